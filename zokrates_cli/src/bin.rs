@@ -48,6 +48,8 @@ fn cli() -> Result<(), String> {
             export_verifier::subcommand(),
             #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
             generate_proof::subcommand(),
+            #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
+            prove::subcommand(),
             print_proof::subcommand(),
             #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
             verify::subcommand()])
@@ -62,6 +64,8 @@ fn cli() -> Result<(), String> {
         ("export-verifier", Some(sub_matches)) => export_verifier::exec(sub_matches),
         #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
         ("generate-proof", Some(sub_matches)) => generate_proof::exec(sub_matches),
+        #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
+        ("prove", Some(sub_matches)) => prove::exec(sub_matches),
         ("print-proof", Some(sub_matches)) => print_proof::exec(sub_matches),
         #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
         ("verify", Some(sub_matches)) => verify::exec(sub_matches),
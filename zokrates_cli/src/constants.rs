@@ -55,3 +55,10 @@ pub const PGHR13: &str = "pghr13";
 pub const GM17: &str = "gm17";
 
 pub const SCHEMES: &[&str] = &[G16, PGHR13, GM17];
+
+pub const JSON: &str = "json";
+pub const HEX: &str = "hex";
+pub const BASE64: &str = "base64";
+pub const BIN: &str = "bin";
+
+pub const PROOF_FORMATS: &[&str] = &[JSON, HEX, BASE64, BIN];
@@ -60,6 +60,32 @@ pub fn subcommand() -> App<'static, 'static> {
         .long("isolate-branches")
         .help("Isolate the execution of branches: a panic in a branch only makes the program panic if this branch is being logically executed")
         .required(false)
+    ).arg(Arg::with_name("reorder-definitions")
+        .long("reorder-definitions")
+        .help("Group independent definitions by the statement consuming them before flattening")
+        .required(false)
+    ).arg(Arg::with_name("max-constraints")
+        .long("max-constraints")
+        .help("Maximum number of constraints supported by the target backend")
+        .value_name("COUNT")
+        .takes_value(true)
+        .required(false)
+    ).arg(Arg::with_name("max-instantiations")
+        .long("max-instantiations")
+        .help("Maximum number of distinct instantiations of generic functions")
+        .value_name("COUNT")
+        .takes_value(true)
+        .required(false)
+    ).arg(Arg::with_name("merge-public-inputs")
+        .long("merge-public-inputs")
+        .help("Merge public inputs asserted to be equal to another public input into it, removing them from the public inputs")
+        .required(false)
+    ).arg(Arg::with_name("wire-manifest")
+        .long("wire-manifest")
+        .help("Path of the manifest listing the role of each wire")
+        .value_name("FILE")
+        .takes_value(true)
+        .required(false)
     ).arg(Arg::with_name("ztf")
         .long("ztf")
         .help("Write human readable output (ztf)")
@@ -128,7 +154,29 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
 
     let config = CompileConfig::default()
         .allow_unconstrained_variables(sub_matches.is_present("allow-unconstrained-variables"))
-        .isolate_branches(sub_matches.is_present("isolate-branches"));
+        .isolate_branches(sub_matches.is_present("isolate-branches"))
+        .reorder_definitions(sub_matches.is_present("reorder-definitions"))
+        .merge_public_inputs(sub_matches.is_present("merge-public-inputs"))
+        .max_constraints(
+            sub_matches
+                .value_of("max-constraints")
+                .map(|limit| {
+                    limit
+                        .parse()
+                        .map_err(|_| format!("Invalid maximum number of constraints: {}", limit))
+                })
+                .transpose()?,
+        )
+        .max_instantiations(
+            sub_matches
+                .value_of("max-instantiations")
+                .map(|limit| {
+                    limit
+                        .parse()
+                        .map_err(|_| format!("Invalid maximum number of instantiations: {}", limit))
+                })
+                .transpose()?,
+        );
 
     let resolver = FileSystemResolver::with_stdlib_root(stdlib_path);
     let artifacts: CompilationArtifacts<T> = compile(source, path, Some(&resolver), &config)
@@ -142,6 +190,10 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
             )
         })?;
 
+    for warning in artifacts.warnings() {
+        println!("Warning: {}", warning);
+    }
+
     let program_flattened = artifacts.prog();
 
     // number of constraints the flattened program will translate to.
@@ -171,6 +223,21 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
 
     println!("Compiled code written to '{}'", bin_output_path.display());
 
+    if let Some(manifest_path) = sub_matches.value_of("wire-manifest") {
+        let manifest_path = Path::new(manifest_path);
+        let manifest_file = File::create(&manifest_path)
+            .map_err(|why| format!("Could not create {}: {}", manifest_path.display(), why))?;
+
+        let mut writer = BufWriter::new(manifest_file);
+        write!(&mut writer, "{}", artifacts.wire_manifest())
+            .map_err(|_| "Unable to write data to file".to_string())?;
+        writer
+            .flush()
+            .map_err(|_| "Unable to flush buffer".to_string())?;
+
+        println!("Wire manifest written to '{}'", manifest_path.display());
+    }
+
     if sub_matches.is_present("ztf") {
         // write human-readable output file
         let hr_output_file = File::create(&hr_output_path)
@@ -186,6 +253,19 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
         println!("Human readable code to '{}'", hr_output_path.display());
     }
 
+    let reduction_stats = artifacts.reduction_stats();
+    println!(
+        "Number of statements after reduction: {}",
+        reduction_stats.statement_count
+    );
+    println!(
+        "Number of inlined calls: {} (maximum depth {})",
+        reduction_stats.inlined_calls, reduction_stats.max_call_depth
+    );
+    println!(
+        "Number of unrolled loop iterations: {}",
+        reduction_stats.unrolled_iterations
+    );
     println!("Number of constraints: {}", num_constraints);
     Ok(())
 }
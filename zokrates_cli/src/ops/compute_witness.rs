@@ -4,7 +4,7 @@ use serde_json::from_reader;
 use std::fs::File;
 use std::io::{stdin, BufReader, BufWriter, Read};
 use std::path::Path;
-use zokrates_abi::Encode;
+use zokrates_abi::{Encode, Inputs};
 use zokrates_core::ir;
 use zokrates_core::ir::ProgEnum;
 use zokrates_core::typed_absy::abi::Abi;
@@ -38,7 +38,12 @@ pub fn subcommand() -> App<'static, 'static> {
         .takes_value(true)
         .required(false)
         .default_value(WITNESS_DEFAULT_PATH)
-    ).arg(Arg::with_name("arguments")
+    ).args(&arguments())
+}
+
+/// The arguments used to pass the inputs of the program's main function
+pub fn arguments() -> Vec<Arg<'static, 'static>> {
+    vec![Arg::with_name("arguments")
         .short("a")
         .long("arguments")
         .help("Arguments for the program's main function, when not using ABI encoding. Expects a space-separated list of field elements like `-a 1 2 3`")
@@ -46,18 +51,17 @@ pub fn subcommand() -> App<'static, 'static> {
         .multiple(true) // allows multiple values
         .required(false)
         .conflicts_with("abi")
-        .conflicts_with("stdin")
-    ).arg(Arg::with_name("abi")
+        .conflicts_with("stdin"),
+    Arg::with_name("abi")
         .long("abi")
         .help("Use ABI encoding. Arguments are expected as a JSON object as specified at zokrates.github.io/toolbox/abi.html#abi-input-format")
         .conflicts_with("arguments")
-        .required(false)
-    ).arg(Arg::with_name("stdin")
+        .required(false),
+    Arg::with_name("stdin")
         .long("stdin")
         .help("Read arguments from stdin")
         .conflicts_with("arguments")
-        .required(false)
-    )
+        .required(false)]
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -86,6 +90,40 @@ fn cli_compute<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Resu
         println!("{}", ir_prog);
     }
 
+    let (signature, arguments) = parse_arguments(&ir_prog, sub_matches)?;
+
+    let witness = compute(&ir_prog, arguments)?;
+
+    use zokrates_abi::Decode;
+
+    let results_json_value: serde_json::Value =
+        zokrates_abi::CheckedValues::decode(witness.return_values(), signature.outputs)
+            .into_serde_json();
+
+    if verbose {
+        println!("\nWitness: \n{}\n", results_json_value);
+    }
+
+    // write witness to file
+    let output_path = Path::new(sub_matches.value_of("output").unwrap());
+    let output_file = File::create(&output_path)
+        .map_err(|why| format!("Could not create {}: {}", output_path.display(), why))?;
+
+    let writer = BufWriter::new(output_file);
+
+    witness
+        .write(writer)
+        .map_err(|why| format!("Could not save witness: {:?}", why))?;
+
+    println!("Witness file written to '{}'", output_path.display());
+    Ok(())
+}
+
+/// Parse the inputs of `ir_prog` passed through `arguments()`, returning them along with the signature they follow
+pub fn parse_arguments<T: Field>(
+    ir_prog: &ir::Prog<T>,
+    sub_matches: &ArgMatches,
+) -> Result<(ConcreteSignature, Inputs<T>), String> {
     let is_stdin = sub_matches.is_present("stdin");
     let is_abi = sub_matches.is_present("abi");
 
@@ -112,8 +150,6 @@ fn cli_compute<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Resu
             .outputs(vec![ConcreteType::FieldElement; ir_prog.main.returns.len()]),
     };
 
-    use zokrates_abi::Inputs;
-
     // get arguments
     let arguments = match is_stdin {
         // take inline arguments
@@ -137,7 +173,7 @@ fn cli_compute<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Resu
                     Ok(_) => {
                         use zokrates_abi::parse_strict;
 
-                        parse_strict(&input, signature.inputs)
+                        parse_strict(&input, signature.inputs.clone())
                             .map(Inputs::Abi)
                             .map_err(|why| why.to_string())
                     }
@@ -162,33 +198,17 @@ fn cli_compute<T: Field>(ir_prog: ir::Prog<T>, sub_matches: &ArgMatches) -> Resu
     }
     .map_err(|e| format!("Could not parse argument: {}", e))?;
 
-    let interpreter = ir::Interpreter::default();
-
-    let witness = interpreter
-        .execute(&ir_prog, &arguments.encode())
-        .map_err(|e| format!("Execution failed: {}", e))?;
-
-    use zokrates_abi::Decode;
-
-    let results_json_value: serde_json::Value =
-        zokrates_abi::CheckedValues::decode(witness.return_values(), signature.outputs)
-            .into_serde_json();
-
-    if verbose {
-        println!("\nWitness: \n{}\n", results_json_value);
-    }
-
-    // write witness to file
-    let output_path = Path::new(sub_matches.value_of("output").unwrap());
-    let output_file = File::create(&output_path)
-        .map_err(|why| format!("Could not create {}: {}", output_path.display(), why))?;
-
-    let writer = BufWriter::new(output_file);
+    Ok((signature, arguments))
+}
 
-    witness
-        .write(writer)
-        .map_err(|why| format!("Could not save witness: {:?}", why))?;
+/// Execute `ir_prog` on `arguments`, returning the witness
+pub fn compute<T: Field>(
+    ir_prog: &ir::Prog<T>,
+    arguments: Inputs<T>,
+) -> Result<ir::Witness<T>, String> {
+    let interpreter = ir::Interpreter::default();
 
-    println!("Witness file written to '{}'", output_path.display());
-    Ok(())
+    interpreter
+        .execute(ir_prog, &arguments.encode())
+        .map_err(|e| format!("Execution failed: {}", e))
 }
@@ -6,11 +6,11 @@ use std::fs::File;
 use std::io::{BufReader, BufWriter, Write};
 use std::path::Path;
 use zokrates_core::proof_system::*;
-use zokrates_field::Bn128Field;
+use zokrates_field::{Bn128Field, Field};
 
 pub fn subcommand() -> App<'static, 'static> {
     SubCommand::with_name("export-verifier")
-        .about("Exports a verifier as Solidity smart contract")
+        .about("Exports a verifier as Solidity or Vyper smart contract")
         .arg(
             Arg::with_name("input")
                 .short("i")
@@ -62,6 +62,22 @@ pub fn subcommand() -> App<'static, 'static> {
                 .default_value("v1")
                 .required(false),
         )
+        .arg(
+            Arg::with_name("dynamic-inputs")
+                .long("dynamic-inputs")
+                .help("Take the public inputs of `verifyTx` as a dynamic array whose length is checked at runtime")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("target")
+                .short("t")
+                .long("target")
+                .help("Language of the exported contract")
+                .takes_value(true)
+                .possible_values(&["solidity", "vyper"])
+                .default_value("solidity")
+                .required(false),
+        )
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -71,17 +87,25 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
     let curve_parameter = CurveParameter::try_from(curve)?;
     let scheme_parameter = SchemeParameter::try_from(scheme)?;
 
-    match (curve_parameter, scheme_parameter) {
-        (CurveParameter::Bn128, SchemeParameter::G16) => {
-            cli_export_verifier::<Bn128Field, G16>(sub_matches)
-        }
-        (CurveParameter::Bn128, SchemeParameter::GM17) => {
-            cli_export_verifier::<Bn128Field, GM17>(sub_matches)
-        }
-        (CurveParameter::Bn128, SchemeParameter::PGHR13) => {
-            cli_export_verifier::<Bn128Field, PGHR13>(sub_matches)
-        }
-        _ => Err(format!("Could not export verifier with given parameters (curve: {}, scheme: {}): not supported", curve, scheme))
+    match sub_matches.value_of("target").unwrap() {
+        "vyper" => match (curve_parameter, scheme_parameter) {
+            (CurveParameter::Bn128, SchemeParameter::G16) => {
+                cli_export_vyper_verifier::<Bn128Field, G16>(sub_matches)
+            }
+            _ => Err(format!("Could not export Vyper verifier with given parameters (curve: {}, scheme: {}): not supported", curve, scheme))
+        },
+        _ => match (curve_parameter, scheme_parameter) {
+            (CurveParameter::Bn128, SchemeParameter::G16) => {
+                cli_export_verifier::<Bn128Field, G16>(sub_matches)
+            }
+            (CurveParameter::Bn128, SchemeParameter::GM17) => {
+                cli_export_verifier::<Bn128Field, GM17>(sub_matches)
+            }
+            (CurveParameter::Bn128, SchemeParameter::PGHR13) => {
+                cli_export_verifier::<Bn128Field, PGHR13>(sub_matches)
+            }
+            _ => Err(format!("Could not export verifier with given parameters (curve: {}, scheme: {}): not supported", curve, scheme))
+        },
     }
 }
 
@@ -90,19 +114,46 @@ fn cli_export_verifier<T: SolidityCompatibleField, S: SolidityCompatibleScheme<T
 ) -> Result<(), String> {
     println!("Exporting verifier...");
 
+    let vk = read_verification_key::<T, S>(sub_matches)?;
+
+    let abi = SolidityAbi::from(sub_matches.value_of("solidity-abi").unwrap())?;
+
+    let inputs = match sub_matches.is_present("dynamic-inputs") {
+        true => SolidityInputs::Dynamic,
+        false => SolidityInputs::Fixed,
+    };
+
+    let verifier = S::export_solidity_verifier(vk, abi, inputs);
+
+    write_verifier(sub_matches, verifier)
+}
+
+fn cli_export_vyper_verifier<T: SolidityCompatibleField, S: VyperCompatibleScheme<T>>(
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    println!("Exporting verifier...");
+
+    let vk = read_verification_key::<T, S>(sub_matches)?;
+
+    let verifier = S::export_vyper_verifier(vk);
+
+    write_verifier(sub_matches, verifier)
+}
+
+fn read_verification_key<T: Field, S: Scheme<T>>(
+    sub_matches: &ArgMatches,
+) -> Result<S::VerificationKey, String> {
     // read vk file
     let input_path = Path::new(sub_matches.value_of("input").unwrap());
     let input_file = File::open(&input_path)
         .map_err(|why| format!("Could not open {}: {}", input_path.display(), why))?;
     let reader = BufReader::new(input_file);
 
-    let vk = serde_json::from_reader(reader)
-        .map_err(|why| format!("Could not deserialize verification key: {}", why))?;
-
-    let abi = SolidityAbi::from(sub_matches.value_of("solidity-abi").unwrap())?;
-
-    let verifier = S::export_solidity_verifier(vk, abi);
+    serde_json::from_reader(reader)
+        .map_err(|why| format!("Could not deserialize verification key: {}", why))
+}
 
+fn write_verifier(sub_matches: &ArgMatches, verifier: String) -> Result<(), String> {
     //write output file
     let output_path = Path::new(sub_matches.value_of("output").unwrap());
     let output_file = File::create(&output_path)
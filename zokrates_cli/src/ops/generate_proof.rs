@@ -44,12 +44,21 @@ pub fn subcommand() -> App<'static, 'static> {
             Arg::with_name("proof-path")
                 .short("j")
                 .long("proof-path")
-                .help("Path of the JSON proof file")
+                .help("Path of the proof file")
                 .value_name("FILE")
                 .takes_value(true)
                 .required(false)
                 .default_value(constants::JSON_PROOF_PATH),
         )
+        .arg(
+            Arg::with_name("proof-format")
+                .long("proof-format")
+                .help("Format of the proof file")
+                .takes_value(true)
+                .required(false)
+                .possible_values(constants::PROOF_FORMATS)
+                .default_value(constants::JSON),
+        )
         .arg(
             Arg::with_name("input")
                 .short("i")
@@ -112,9 +121,9 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, _, SchemeParameter::GM17) => match prog {
             ProgEnum::Bls12_377Program(p) => cli_generate_proof::<_, GM17, Ark>(p, sub_matches),
+            ProgEnum::Bls12_381Program(p) => cli_generate_proof::<_, GM17, Ark>(p, sub_matches),
             ProgEnum::Bw6_761Program(p) => cli_generate_proof::<_, GM17, Ark>(p, sub_matches),
             ProgEnum::Bn128Program(p) => cli_generate_proof::<_, GM17, Ark>(p, sub_matches),
-            _ => unreachable!(),
         },
         #[cfg(feature = "libsnark")]
         Parameters(BackendParameter::Libsnark, CurveParameter::Bn128, SchemeParameter::GM17) => {
@@ -152,6 +161,16 @@ fn cli_generate_proof<T: Field, S: Scheme<T>, B: Backend<T, S>>(
     let witness = ir::Witness::read(witness_file)
         .map_err(|why| format!("Could not load witness: {:?}", why))?;
 
+    generate_proof::<T, S, B>(program, witness, sub_matches)
+}
+
+/// Generate a proof for `program` and `witness` using the proving key and writing the proof
+/// at the paths found in `sub_matches`
+pub fn generate_proof<T: Field, S: Scheme<T>, B: Backend<T, S>>(
+    program: ir::Prog<T>,
+    witness: ir::Witness<T>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
     let pk_path = Path::new(sub_matches.value_of("proving-key-path").unwrap());
     let proof_path = Path::new(sub_matches.value_of("proof-path").unwrap());
 
@@ -164,16 +183,20 @@ fn cli_generate_proof<T: Field, S: Scheme<T>, B: Backend<T, S>>(
         .read_to_end(&mut pk)
         .map_err(|why| format!("Could not read {}: {}", pk_path.display(), why))?;
 
+    let format = ProofFormat::try_from(sub_matches.value_of("proof-format").unwrap())?;
+
     let proof = B::generate_proof(program, witness, pk);
     let mut proof_file = File::create(proof_path).unwrap();
 
-    let proof = serde_json::to_string_pretty(&proof).unwrap();
     proof_file
-        .write(proof.as_bytes())
+        .write(&proof.to_bytes(format))
         .map_err(|why| format!("Could not write to {}: {}", proof_path.display(), why))?;
 
     if sub_matches.is_present("verbose") {
-        println!("Proof:\n{}", proof);
+        println!(
+            "Proof:\n{}",
+            String::from_utf8(proof.to_bytes(ProofFormat::Json)).unwrap()
+        );
     }
 
     println!("Proof written to '{}'", proof_path.display());
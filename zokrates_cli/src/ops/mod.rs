@@ -6,6 +6,8 @@ pub mod export_verifier;
 pub mod generate_proof;
 pub mod print_proof;
 #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
+pub mod prove;
+#[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
 pub mod setup;
 #[cfg(any(feature = "bellman", feature = "ark", feature = "libsnark"))]
 pub mod verify;
@@ -0,0 +1,160 @@
+use crate::constants;
+use crate::helpers::*;
+use crate::ops::compute_witness::{arguments, compute, parse_arguments};
+use crate::ops::generate_proof::generate_proof;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use zokrates_core::ir;
+use zokrates_core::ir::ProgEnum;
+#[cfg(feature = "ark")]
+use zokrates_core::proof_system::ark::Ark;
+#[cfg(feature = "bellman")]
+use zokrates_core::proof_system::bellman::Bellman;
+#[cfg(feature = "libsnark")]
+use zokrates_core::proof_system::libsnark::Libsnark;
+use zokrates_core::proof_system::*;
+use zokrates_field::Field;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("prove")
+        .about("Calculates a witness and a proof for a given constraint system, without writing the witness to disk")
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the binary")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(constants::FLATTENED_CODE_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("abi-spec")
+                .short("s")
+                .long("abi-spec")
+                .help("Path of the ABI specification")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(constants::ABI_SPEC_DEFAULT_PATH),
+        )
+        .args(&arguments())
+        .arg(
+            Arg::with_name("proving-key-path")
+                .short("p")
+                .long("proving-key-path")
+                .help("Path of the proving key file")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(constants::PROVING_KEY_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("proof-path")
+                .short("j")
+                .long("proof-path")
+                .help("Path of the proof file")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(constants::JSON_PROOF_PATH),
+        )
+        .arg(
+            Arg::with_name("proof-format")
+                .long("proof-format")
+                .help("Format of the proof file")
+                .takes_value(true)
+                .required(false)
+                .possible_values(constants::PROOF_FORMATS)
+                .default_value(constants::JSON),
+        )
+        .arg(
+            Arg::with_name("backend")
+                .short("b")
+                .long("backend")
+                .help("Backend to use")
+                .takes_value(true)
+                .required(false)
+                .possible_values(constants::BACKENDS)
+                .default_value(constants::BELLMAN),
+        )
+        .arg(
+            Arg::with_name("proving-scheme")
+                .long("proving-scheme")
+                .help("Proving scheme to use to generate the proof")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .possible_values(constants::SCHEMES)
+                .default_value(constants::G16),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    let program_path = Path::new(sub_matches.value_of("input").unwrap());
+    let program_file = File::open(&program_path)
+        .map_err(|why| format!("Could not open {}: {}", program_path.display(), why))?;
+
+    let mut reader = BufReader::new(program_file);
+    let prog = ProgEnum::deserialize(&mut reader)?;
+
+    let parameters = Parameters::try_from((
+        sub_matches.value_of("backend").unwrap(),
+        match prog {
+            ProgEnum::Bn128Program(_) => constants::BN128,
+            ProgEnum::Bls12_381Program(_) => constants::BLS12_381,
+            ProgEnum::Bls12_377Program(_) => constants::BLS12_377,
+            ProgEnum::Bw6_761Program(_) => constants::BW6_761,
+        },
+        sub_matches.value_of("proving-scheme").unwrap(),
+    ))?;
+
+    match parameters {
+        #[cfg(feature = "bellman")]
+        Parameters(BackendParameter::Bellman, _, SchemeParameter::G16) => match prog {
+            ProgEnum::Bn128Program(p) => cli_prove::<_, G16, Bellman>(p, sub_matches),
+            ProgEnum::Bls12_381Program(p) => cli_prove::<_, G16, Bellman>(p, sub_matches),
+            _ => unreachable!(),
+        },
+        #[cfg(feature = "ark")]
+        Parameters(BackendParameter::Ark, _, SchemeParameter::GM17) => match prog {
+            ProgEnum::Bls12_377Program(p) => cli_prove::<_, GM17, Ark>(p, sub_matches),
+            ProgEnum::Bls12_381Program(p) => cli_prove::<_, GM17, Ark>(p, sub_matches),
+            ProgEnum::Bw6_761Program(p) => cli_prove::<_, GM17, Ark>(p, sub_matches),
+            ProgEnum::Bn128Program(p) => cli_prove::<_, GM17, Ark>(p, sub_matches),
+        },
+        #[cfg(feature = "libsnark")]
+        Parameters(BackendParameter::Libsnark, CurveParameter::Bn128, SchemeParameter::GM17) => {
+            match prog {
+                ProgEnum::Bn128Program(p) => cli_prove::<_, GM17, Libsnark>(p, sub_matches),
+                _ => unreachable!(),
+            }
+        }
+        #[cfg(feature = "libsnark")]
+        Parameters(BackendParameter::Libsnark, CurveParameter::Bn128, SchemeParameter::PGHR13) => {
+            match prog {
+                ProgEnum::Bn128Program(p) => cli_prove::<_, PGHR13, Libsnark>(p, sub_matches),
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn cli_prove<T: Field, S: Scheme<T>, B: Backend<T, S>>(
+    program: ir::Prog<T>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    println!("Computing witness...");
+
+    let (_, arguments) = parse_arguments(&program, sub_matches)?;
+
+    let witness = compute(&program, arguments)?;
+
+    println!("Generating proof...");
+
+    generate_proof::<T, S, B>(program, witness, sub_matches)
+}
@@ -102,9 +102,9 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, _, SchemeParameter::GM17) => match prog {
             ProgEnum::Bls12_377Program(p) => cli_setup::<_, GM17, Ark>(p, sub_matches),
+            ProgEnum::Bls12_381Program(p) => cli_setup::<_, GM17, Ark>(p, sub_matches),
             ProgEnum::Bw6_761Program(p) => cli_setup::<_, GM17, Ark>(p, sub_matches),
             ProgEnum::Bn128Program(p) => cli_setup::<_, GM17, Ark>(p, sub_matches),
-            _ => unreachable!(),
         },
         #[cfg(feature = "libsnark")]
         Parameters(BackendParameter::Libsnark, CurveParameter::Bn128, SchemeParameter::GM17) => {
@@ -3,7 +3,7 @@ use crate::helpers::*;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use std::convert::TryFrom;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Read};
 use std::path::Path;
 #[cfg(feature = "ark")]
 use zokrates_core::proof_system::ark::Ark;
@@ -21,11 +21,18 @@ pub fn subcommand() -> App<'static, 'static> {
         .arg(Arg::with_name("proof-path")
             .short("j")
             .long("proof-path")
-            .help("Path of the JSON proof file")
+            .help("Path of the proof file")
             .value_name("FILE")
             .takes_value(true)
             .required(false)
             .default_value(constants::JSON_PROOF_PATH)
+        ).arg(Arg::with_name("proof-format")
+            .long("proof-format")
+            .help("Format of the proof file")
+            .takes_value(true)
+            .required(false)
+            .possible_values(constants::PROOF_FORMATS)
+            .default_value(constants::JSON)
         ).arg(Arg::with_name("verification-key-path")
         .short("v")
         .long("verification-key-path")
@@ -82,6 +89,10 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
             cli_verify::<Bls12_377Field, GM17, Ark>(sub_matches)
         }
         #[cfg(feature = "ark")]
+        Parameters(BackendParameter::Ark, CurveParameter::Bls12_381, SchemeParameter::GM17) => {
+            cli_verify::<Bls12_381Field, GM17, Ark>(sub_matches)
+        }
+        #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bw6_761, SchemeParameter::GM17) => {
             cli_verify::<Bw6_761Field, GM17, Ark>(sub_matches)
         }
@@ -116,8 +127,14 @@ fn cli_verify<T: Field, S: Scheme<T>, B: Backend<T, S>>(
     let proof_file = File::open(&proof_path)
         .map_err(|why| format!("Could not open {}: {}", proof_path.display(), why))?;
 
-    let proof_reader = BufReader::new(proof_file);
-    let proof = serde_json::from_reader(proof_reader)
+    let format = ProofFormat::try_from(sub_matches.value_of("proof-format").unwrap())?;
+
+    let mut proof_bytes = Vec::new();
+    BufReader::new(proof_file)
+        .read_to_end(&mut proof_bytes)
+        .map_err(|why| format!("Could not read {}: {}", proof_path.display(), why))?;
+
+    let proof = Proof::from_bytes(&proof_bytes, format)
         .map_err(|why| format!("Could not deserialize proof: {}", why))?;
 
     println!("Performing verification...");
@@ -75,6 +75,7 @@ mod integration {
         let witness_path = tmp_base.join(program_name).join("witness");
         let inline_witness_path = tmp_base.join(program_name).join("inline_witness");
         let proof_path = tmp_base.join(program_name).join("proof.json");
+        let prove_proof_path = tmp_base.join(program_name).join("prove_proof.json");
         let verification_key_path = tmp_base
             .join(program_name)
             .join("verification")
@@ -279,6 +280,53 @@ mod integration {
                 .succeeds()
                 .unwrap();
 
+                // PROVE
+                assert_cli::Assert::command(&[
+                    "../target/release/zokrates",
+                    "prove",
+                    "-i",
+                    flattened_path.to_str().unwrap(),
+                    "-s",
+                    abi_spec_path.to_str().unwrap(),
+                    "--stdin",
+                    "--abi",
+                    "-p",
+                    proving_key_path.to_str().unwrap(),
+                    "--backend",
+                    backend,
+                    "--proving-scheme",
+                    scheme,
+                    "-j",
+                    prove_proof_path.to_str().unwrap(),
+                ])
+                .stdin(&json_input_str)
+                .succeeds()
+                .unwrap();
+
+                // proofs are randomized, so we check that the single step proof verifies
+                // and has the same public inputs as the one from the two step flow
+                assert_cli::Assert::command(&[
+                    "../target/release/zokrates",
+                    "verify",
+                    "--backend",
+                    backend,
+                    "--proving-scheme",
+                    scheme,
+                    "-j",
+                    prove_proof_path.to_str().unwrap(),
+                    "-v",
+                    verification_key_path.to_str().unwrap(),
+                ])
+                .succeeds()
+                .unwrap();
+
+                let public_inputs = |path: &Path| {
+                    let proof: serde_json::Value = from_reader(File::open(path).unwrap()).unwrap();
+                    proof["inputs"].clone()
+                };
+
+                assert_eq!(public_inputs(&proof_path), public_inputs(&prove_proof_path));
+
                 if backend != "ark" {
                     for abi_version in &["v1", "v2"] {
                         // EXPORT-VERIFIER
@@ -11,6 +11,7 @@ use crate::macros;
 use crate::semantics::{self, Checker};
 use crate::static_analysis;
 use crate::static_analysis::Analyse;
+use crate::static_analysis::{ReductionStats, Warning};
 use crate::typed_absy::abi::Abi;
 use crate::zir::ZirProgram;
 use macros::process_macros;
@@ -28,6 +29,8 @@ use zokrates_pest_ast as pest;
 pub struct CompilationArtifacts<T: Field> {
     prog: ir::Prog<T>,
     abi: Abi,
+    reduction_stats: ReductionStats,
+    warnings: Vec<Warning>,
 }
 
 impl<T: Field> CompilationArtifacts<T> {
@@ -38,6 +41,18 @@ impl<T: Field> CompilationArtifacts<T> {
     pub fn abi(&self) -> &Abi {
         &self.abi
     }
+
+    pub fn reduction_stats(&self) -> &ReductionStats {
+        &self.reduction_stats
+    }
+
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    pub fn wire_manifest(&self) -> ir::WireManifest {
+        ir::WireManifest::new(&self.prog, &self.abi)
+    }
 }
 
 #[derive(Debug)]
@@ -57,6 +72,7 @@ pub enum CompileErrorInner {
     SemanticError(semantics::ErrorInner),
     ReadError(io::Error),
     AnalysisError(static_analysis::Error),
+    ExceedsBackendLimit(usize, usize),
 }
 
 impl CompileErrorInner {
@@ -158,14 +174,24 @@ impl fmt::Display for CompileErrorInner {
                 write!(f, "{}\n\t{}", location, e.message())
             }
             CompileErrorInner::AnalysisError(ref e) => write!(f, "\n\t{}", e),
+            CompileErrorInner::ExceedsBackendLimit(count, limit) => write!(
+                f,
+                "\n\tThe program has {} constraints, which exceeds the maximum of {} supported by the backend",
+                count, limit
+            ),
         }
     }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct CompileConfig {
     pub allow_unconstrained_variables: bool,
     pub isolate_branches: bool,
+    pub reorder_definitions: bool,
+    pub max_constraints: Option<usize>,
+    pub max_instantiations: Option<usize>,
+    pub merge_public_inputs: bool,
 }
 
 impl CompileConfig {
@@ -177,6 +203,22 @@ impl CompileConfig {
         self.isolate_branches = flag;
         self
     }
+    pub fn reorder_definitions(mut self, flag: bool) -> Self {
+        self.reorder_definitions = flag;
+        self
+    }
+    pub fn max_constraints(mut self, limit: Option<usize>) -> Self {
+        self.max_constraints = limit;
+        self
+    }
+    pub fn max_instantiations(mut self, limit: Option<usize>) -> Self {
+        self.max_instantiations = limit;
+        self
+    }
+    pub fn merge_public_inputs(mut self, flag: bool) -> Self {
+        self.merge_public_inputs = flag;
+        self
+    }
 }
 
 type FilePath = PathBuf;
@@ -189,7 +231,8 @@ pub fn compile<T: Field, E: Into<imports::Error>>(
 ) -> Result<CompilationArtifacts<T>, CompileErrors> {
     let arena = Arena::new();
 
-    let (typed_ast, abi) = check_with_arena(source, location, resolver, config, &arena)?;
+    let (typed_ast, abi, reduction_stats, warnings) =
+        check_with_arena(source, location.clone(), resolver, config, &arena)?;
 
     // flatten input program
     let program_flattened = Flattener::flatten(typed_ast, config);
@@ -206,9 +249,21 @@ pub fn compile<T: Field, E: Into<imports::Error>>(
     // analyse (check constraints)
     let optimized_ir_prog = optimized_ir_prog.analyse();
 
+    // check the program fits in the backend
+    if let Some(limit) = config.max_constraints {
+        let count = optimized_ir_prog.constraint_count();
+        if count > limit {
+            return Err(CompileErrorInner::ExceedsBackendLimit(count, limit)
+                .in_file(&location)
+                .into());
+        }
+    }
+
     Ok(CompilationArtifacts {
         prog: optimized_ir_prog,
         abi,
+        reduction_stats,
+        warnings,
     })
 }
 
@@ -229,7 +284,7 @@ fn check_with_arena<'ast, T: Field, E: Into<imports::Error>>(
     resolver: Option<&dyn Resolver<E>>,
     config: &CompileConfig,
     arena: &'ast Arena<String>,
-) -> Result<(ZirProgram<'ast, T>, Abi), CompileErrors> {
+) -> Result<(ZirProgram<'ast, T>, Abi, ReductionStats, Vec<Warning>), CompileErrors> {
     let source = arena.alloc(source);
     let compiled = compile_program::<T, E>(source, location, resolver, &arena)?;
 
@@ -434,5 +489,113 @@ struct Bar { field a }
                 }
             );
         }
+
+        #[test]
+        fn struct_member_order() {
+            // two structs with the same members declared in a different order keep their declaration order
+
+            let main = r#"
+struct Foo {
+    field a
+    bool b
+}
+struct Bar {
+    bool b
+    field a
+}
+def main(Foo foo, Bar bar):
+    return
+"#;
+
+            let artifacts = compile::<Bn128Field, io::Error>(
+                main.to_string(),
+                "main".into(),
+                None,
+                &CompileConfig::default(),
+            )
+            .unwrap();
+
+            let a = || ConcreteStructMember {
+                id: "a".into(),
+                ty: box ConcreteType::FieldElement,
+            };
+            let b = || ConcreteStructMember {
+                id: "b".into(),
+                ty: box ConcreteType::Boolean,
+            };
+
+            assert_eq!(
+                artifacts.abi,
+                Abi {
+                    inputs: vec![
+                        AbiInput {
+                            name: "foo".into(),
+                            public: true,
+                            ty: ConcreteType::Struct(ConcreteStructType::new(
+                                "main".into(),
+                                "Foo".into(),
+                                vec![a(), b()]
+                            ))
+                        },
+                        AbiInput {
+                            name: "bar".into(),
+                            public: true,
+                            ty: ConcreteType::Struct(ConcreteStructType::new(
+                                "main".into(),
+                                "Bar".into(),
+                                vec![b(), a()]
+                            ))
+                        }
+                    ],
+                    outputs: vec![]
+                }
+            );
+        }
+
+        #[test]
+        fn merge_public_inputs() {
+            // with the option, a public input asserted to be equal to another one is removed from the inputs
+
+            let main = r#"
+def main(field a, field b, private field c) -> field:
+    assert(a == b)
+    return a * b + c
+"#;
+
+            let artifacts = compile::<Bn128Field, io::Error>(
+                main.to_string(),
+                "main".into(),
+                None,
+                &CompileConfig::default().merge_public_inputs(true),
+            )
+            .unwrap();
+
+            assert_eq!(
+                artifacts.abi,
+                Abi {
+                    inputs: vec![
+                        AbiInput {
+                            name: "a".into(),
+                            public: true,
+                            ty: ConcreteType::FieldElement
+                        },
+                        AbiInput {
+                            name: "c".into(),
+                            public: false,
+                            ty: ConcreteType::FieldElement
+                        }
+                    ],
+                    outputs: vec![ConcreteType::FieldElement]
+                }
+            );
+            assert_eq!(artifacts.prog().private, vec![false, true]);
+            assert_eq!(
+                artifacts.warnings(),
+                &[Warning::MergedPublicInput {
+                    kept: "a".into(),
+                    removed: "b".into()
+                }]
+            );
+        }
     }
 }
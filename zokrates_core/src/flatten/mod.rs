@@ -307,9 +307,6 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         left: FlatExpression<T>,
         right: FlatExpression<T>,
     ) -> FlatExpression<T> {
-        let left = self.define(left, statements_flattened);
-        let right = self.define(right, statements_flattened);
-
         // Wanted: (Y = (X != 0) ? 1 : 0)
         // X = a - b
         // # Y = if X == 0 then 0 else 1 fi
@@ -317,7 +314,30 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         // Y == X * M
         // 0 == (1-Y) * X
 
-        let x = FlatExpression::Sub(box left.into(), box right.into());
+        let x = match (left, right) {
+            // both sides are constant, no constraint is required
+            (FlatExpression::Number(left), FlatExpression::Number(right)) => {
+                return FlatExpression::Number(if left == right { T::one() } else { T::zero() });
+            }
+            // comparing against zero is the is-zero gadget: X is the other operand itself
+            (e, FlatExpression::Number(n)) | (FlatExpression::Number(n), e) if n == T::zero() => {
+                self.define(e, statements_flattened).into()
+            }
+            // constants do not need to be defined as X is linear in them
+            (FlatExpression::Number(n), e) => {
+                let e = self.define(e, statements_flattened);
+                FlatExpression::Sub(box FlatExpression::Number(n), box e.into())
+            }
+            (e, FlatExpression::Number(n)) => {
+                let e = self.define(e, statements_flattened);
+                FlatExpression::Sub(box e.into(), box FlatExpression::Number(n))
+            }
+            (left, right) => {
+                let left = self.define(left, statements_flattened);
+                let right = self.define(right, statements_flattened);
+                FlatExpression::Sub(box left.into(), box right.into())
+            }
+        };
 
         let name_y = self.use_sym();
         let name_m = self.use_sym();
@@ -2168,6 +2188,10 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                     alternative,
                 )
                 .get_field_unchecked(),
+            // booleans are flattened to 0 or 1, so the conversion is free
+            FieldElementExpression::BoolToField(box e) => {
+                self.flatten_boolean_expression(statements_flattened, e)
+            }
         }
     }
 
@@ -2676,6 +2700,83 @@ mod tests {
         assert_eq!(flat, expected);
     }
 
+    #[test]
+    fn is_zero() {
+        // def main(field x) -> bool:
+        //     return x == 0
+
+        // def main(_0) -> (1):
+        //     # _1, _2 = ConditionEq(_0)
+        //     _1 == (_0 * _2)
+        //     0 == ((1 - _1) * _0)
+        //     return 1 - _1
+        let function = ZirFunction::<Bn128Field> {
+            arguments: vec![Parameter::private(Variable::field_element("x"))],
+            statements: vec![ZirStatement::Return(vec![BooleanExpression::FieldEq(
+                box FieldElementExpression::Identifier("x".into()),
+                box FieldElementExpression::Number(Bn128Field::from(0)),
+            )
+            .into()])],
+            signature: Signature {
+                inputs: vec![Type::FieldElement],
+                outputs: vec![Type::Boolean],
+            },
+        };
+
+        let config = CompileConfig::default();
+        let mut flattener = Flattener::new(&config);
+
+        let flat = flattener.flatten_function(function);
+
+        let is_not_zero = FlatExpression::Sub(
+            box FlatExpression::Number(Bn128Field::from(1)),
+            box FlatExpression::Identifier(FlatVariable::new(1)),
+        );
+
+        let expected = FlatFunction {
+            arguments: vec![FlatParameter::private(FlatVariable::new(0))],
+            statements: vec![
+                FlatStatement::Directive(FlatDirective::new(
+                    vec![FlatVariable::new(1), FlatVariable::new(2)],
+                    Solver::ConditionEq,
+                    vec![FlatExpression::Identifier(FlatVariable::new(0))],
+                )),
+                FlatStatement::Condition(
+                    FlatExpression::Identifier(FlatVariable::new(1)),
+                    FlatExpression::Mult(
+                        box FlatExpression::Identifier(FlatVariable::new(0)),
+                        box FlatExpression::Identifier(FlatVariable::new(2)),
+                    ),
+                ),
+                FlatStatement::Condition(
+                    FlatExpression::Number(Bn128Field::from(0)),
+                    FlatExpression::Mult(
+                        box is_not_zero.clone(),
+                        box FlatExpression::Identifier(FlatVariable::new(0)),
+                    ),
+                ),
+                FlatStatement::Return(FlatExpressionList {
+                    expressions: vec![is_not_zero],
+                }),
+            ],
+        };
+
+        assert_eq!(flat, expected);
+
+        // the constant case is folded without introducing any constraint
+        let mut statements = FlatStatements::new();
+        let folded = flattener.flatten_boolean_expression(
+            &mut statements,
+            BooleanExpression::FieldEq(
+                box FieldElementExpression::Number(Bn128Field::from(0)),
+                box FieldElementExpression::Number(Bn128Field::from(0)),
+            ),
+        );
+
+        assert_eq!(folded, FlatExpression::Number(Bn128Field::from(1)));
+        assert!(statements.is_empty());
+    }
+
     #[test]
     fn assertion_uint_eq() {
         // def main():
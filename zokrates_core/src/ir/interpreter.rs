@@ -97,6 +97,23 @@ impl Interpreter {
         Ok(Witness(witness))
     }
 
+    /// Lazily execute `program` once for each set of inputs.
+    /// Each execution is independent, so a failure on one set of inputs does not affect the others.
+    /// Note that `execute` does no per-program setup, so this is equivalent to calling it in a loop.
+    pub fn execute_many<'a, T: Field, I>(
+        &'a self,
+        program: &'a Prog<T>,
+        inputs: I,
+    ) -> impl Iterator<Item = ExecutionResult<T>> + 'a
+    where
+        I: IntoIterator<Item = Vec<T>>,
+        I::IntoIter: 'a,
+    {
+        inputs
+            .into_iter()
+            .map(move |inputs| self.execute(program, &inputs))
+    }
+
     fn try_solve_out_of_range<T: Field>(d: &Directive<T>, witness: &mut BTreeMap<FlatVariable, T>) {
         use num::traits::Pow;
 
@@ -356,6 +373,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn execute_many() {
+        use crate::ir::Function;
+
+        // def main(x) -> x * x
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::constraint(
+                    QuadComb::from_linear_combinations(
+                        FlatVariable::new(0).into(),
+                        FlatVariable::new(0).into(),
+                    ),
+                    FlatVariable::public(0),
+                )],
+            },
+            private: vec![true],
+        };
+
+        let inputs = vec![
+            vec![Bn128Field::from(0)],
+            vec![Bn128Field::from(3)],
+            vec![Bn128Field::from(42)],
+            vec![Bn128Field::from(1), Bn128Field::from(2)],
+        ];
+
+        let interpreter = Interpreter::default();
+
+        let batch: Vec<_> = interpreter.execute_many(&program, inputs.clone()).collect();
+
+        let individual: Vec<_> = inputs
+            .iter()
+            .map(|i| interpreter.execute(&program, i))
+            .collect();
+
+        assert_eq!(batch, individual);
+        assert_eq!(
+            batch[1].as_ref().unwrap().return_values(),
+            vec![Bn128Field::from(9)]
+        );
+        assert!(batch[3].is_err());
+    }
+
     #[test]
     fn bits_of_one() {
         let inputs = vec![Bn128Field::from(1)];
@@ -0,0 +1,200 @@
+//! Module containing the manifest of the wires of a program
+//!
+//! The manifest lists each wire of the constraint system with its role and, for inputs, the name of the
+//! parameter of `main` it comes from. Wires are listed in a fixed order: the one-constant, the inputs in the
+//! order of the parameters, the outputs, then the intermediate wires by increasing index. Two compilations
+//! of the same source produce identical manifests.
+
+use crate::flat_absy::FlatVariable;
+use crate::ir::{Prog, Statement};
+use crate::typed_absy::abi::Abi;
+use std::collections::BTreeSet;
+use std::fmt;
+use zokrates_field::Field;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireRole {
+    One,
+    PublicInput,
+    PrivateInput,
+    Output,
+    Intermediate,
+}
+
+impl fmt::Display for WireRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WireRole::One => write!(f, "one"),
+            WireRole::PublicInput => write!(f, "public_input"),
+            WireRole::PrivateInput => write!(f, "private_input"),
+            WireRole::Output => write!(f, "output"),
+            WireRole::Intermediate => write!(f, "intermediate"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wire {
+    pub variable: FlatVariable,
+    pub role: WireRole,
+    pub source: Option<String>,
+}
+
+impl fmt::Display for Wire {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.variable, self.role)?;
+        match &self.source {
+            Some(source) => write!(f, " {}", source),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireManifest(pub Vec<Wire>);
+
+impl WireManifest {
+    pub fn new<T: Field>(prog: &Prog<T>, abi: &Abi) -> Self {
+        // the source of each input wire: inputs of size greater than one are indexed by flattened element
+        let sources = abi.inputs.iter().flat_map(|input| {
+            let count = input.ty.get_primitive_count();
+            (0..count).map(move |i| match count {
+                1 => input.name.clone(),
+                _ => format!("{}[{}]", input.name, i),
+            })
+        });
+
+        let inputs = prog
+            .parameters()
+            .into_iter()
+            .zip(sources)
+            .map(|(parameter, source)| Wire {
+                variable: parameter.id,
+                role: match parameter.private {
+                    true => WireRole::PrivateInput,
+                    false => WireRole::PublicInput,
+                },
+                source: Some(source),
+            });
+
+        let outputs = prog.main.returns.iter().map(|v| Wire {
+            variable: *v,
+            role: WireRole::Output,
+            source: None,
+        });
+
+        // a sorted set makes the order of the intermediate wires independent of the order of the statements
+        let mut intermediates = BTreeSet::new();
+        for statement in &prog.main.statements {
+            match statement {
+                Statement::Constraint(quad, lin) => {
+                    intermediates.extend(
+                        quad.left
+                            .0
+                            .iter()
+                            .chain(quad.right.0.iter())
+                            .chain(lin.0.iter())
+                            .map(|(v, _)| *v),
+                    );
+                }
+                Statement::Directive(d) => {
+                    intermediates.extend(
+                        d.inputs
+                            .iter()
+                            .flat_map(|q| q.left.0.iter().chain(q.right.0.iter()))
+                            .map(|(v, _)| *v),
+                    );
+                    intermediates.extend(d.outputs.iter().cloned());
+                }
+            }
+        }
+
+        for v in prog.main.arguments.iter() {
+            intermediates.remove(v);
+        }
+
+        let intermediates = intermediates
+            .into_iter()
+            .filter(|v| *v != FlatVariable::one() && !v.is_output())
+            .map(|v| Wire {
+                variable: v,
+                role: WireRole::Intermediate,
+                source: None,
+            });
+
+        WireManifest(
+            std::iter::once(Wire {
+                variable: FlatVariable::one(),
+                role: WireRole::One,
+                source: None,
+            })
+            .chain(inputs)
+            .chain(outputs)
+            .chain(intermediates)
+            .collect(),
+        )
+    }
+}
+
+impl fmt::Display for WireManifest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for wire in &self.0 {
+            writeln!(f, "{}", wire)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Function, QuadComb};
+    use crate::typed_absy::abi::AbiInput;
+    use crate::typed_absy::types::ConcreteType;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn roles() {
+        // def main(private field a, field b) -> field:
+        //     return a * b
+        let prog: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: "main".into(),
+                statements: vec![
+                    Statement::constraint(
+                        QuadComb::from_linear_combinations(
+                            FlatVariable::new(0).into(),
+                            FlatVariable::new(1).into(),
+                        ),
+                        FlatVariable::new(2),
+                    ),
+                    Statement::definition(FlatVariable::public(0), FlatVariable::new(2)),
+                ],
+                arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+                returns: vec![FlatVariable::public(0)],
+            },
+            private: vec![true, false],
+        };
+
+        let abi = Abi {
+            inputs: vec![
+                AbiInput {
+                    name: "a".into(),
+                    public: false,
+                    ty: ConcreteType::FieldElement,
+                },
+                AbiInput {
+                    name: "b".into(),
+                    public: true,
+                    ty: ConcreteType::FieldElement,
+                },
+            ],
+            outputs: vec![ConcreteType::FieldElement],
+        };
+
+        assert_eq!(
+            WireManifest::new(&prog, &abi).to_string(),
+            "~one one\n_0 private_input a\n_1 public_input b\n~out_0 output\n_2 intermediate\n"
+        );
+    }
+}
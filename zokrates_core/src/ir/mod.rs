@@ -2,6 +2,7 @@ use crate::flat_absy::flat_parameter::FlatParameter;
 use crate::flat_absy::FlatVariable;
 use crate::solvers::Solver;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt;
 use std::hash::Hash;
 use zokrates_field::Field;
@@ -10,6 +11,7 @@ mod expression;
 pub mod folder;
 mod from_flat;
 mod interpreter;
+mod manifest;
 mod serialize;
 mod witness;
 
@@ -18,6 +20,7 @@ pub use self::expression::{CanonicalLinComb, LinComb};
 pub use self::serialize::ProgEnum;
 
 pub use self::interpreter::{Error, ExecutionResult, Interpreter};
+pub use self::manifest::{Wire, WireManifest, WireRole};
 pub use self::witness::Witness;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Hash, PartialEq, Eq)]
@@ -106,6 +109,17 @@ impl<T: Field> fmt::Display for Function<T> {
     }
 }
 
+/// Statistics about the size of a program, cheap to compute before running a setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IrStats {
+    /// The number of constraints
+    pub constraints: usize,
+    /// The number of distinct variables, excluding the constant `~one`
+    pub variables: usize,
+    /// The number of public inputs, including the return values
+    pub public_inputs: usize,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Prog<T> {
     pub main: Function<T>,
@@ -121,6 +135,46 @@ impl<T: Field> Prog<T> {
             .count()
     }
 
+    pub fn statistics(&self) -> IrStats {
+        let mut variables: HashSet<FlatVariable> = self
+            .main
+            .arguments
+            .iter()
+            .chain(self.main.returns.iter())
+            .cloned()
+            .collect();
+
+        for statement in &self.main.statements {
+            match statement {
+                Statement::Constraint(quad, lin) => variables.extend(
+                    quad.left
+                        .0
+                        .iter()
+                        .chain(quad.right.0.iter())
+                        .chain(lin.0.iter())
+                        .map(|(v, _)| *v),
+                ),
+                Statement::Directive(d) => {
+                    variables.extend(
+                        d.inputs
+                            .iter()
+                            .flat_map(|q| q.left.0.iter().chain(q.right.0.iter()))
+                            .map(|(v, _)| *v),
+                    );
+                    variables.extend(d.outputs.iter().cloned());
+                }
+            }
+        }
+
+        variables.remove(&FlatVariable::one());
+
+        IrStats {
+            constraints: self.constraint_count(),
+            variables: variables.len(),
+            public_inputs: self.private.iter().filter(|p| !**p).count() + self.main.returns.len(),
+        }
+    }
+
     pub fn arguments_count(&self) -> usize {
         self.private.len()
     }
@@ -176,4 +230,111 @@ mod tests {
             assert_eq!(format!("{}", c), "(1 * _42) * (1 * _42) == 1 * _42")
         }
     }
+
+    mod statistics {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            let prog: Prog<Bn128Field> = Prog {
+                main: Function {
+                    id: "main".into(),
+                    statements: vec![],
+                    arguments: vec![],
+                    returns: vec![],
+                },
+                private: vec![],
+            };
+
+            assert_eq!(
+                prog.statistics(),
+                IrStats {
+                    constraints: 0,
+                    variables: 0,
+                    public_inputs: 0
+                }
+            );
+        }
+
+        #[test]
+        fn directive() {
+            // def main(private _0) -> (1):
+            //      # _1 = Bits(1)(_0)
+            //      _1 * _1 == _1
+            //      ~out_0 == _1
+            let prog: Prog<Bn128Field> = Prog {
+                main: Function {
+                    id: "main".into(),
+                    statements: vec![
+                        Statement::Directive(Directive {
+                            inputs: vec![FlatVariable::new(0).into()],
+                            outputs: vec![FlatVariable::new(1)],
+                            solver: Solver::Bits(1),
+                        }),
+                        Statement::constraint(
+                            QuadComb::from_linear_combinations(
+                                FlatVariable::new(1).into(),
+                                FlatVariable::new(1).into(),
+                            ),
+                            FlatVariable::new(1),
+                        ),
+                        Statement::definition(FlatVariable::public(0), FlatVariable::new(1)),
+                    ],
+                    arguments: vec![FlatVariable::new(0)],
+                    returns: vec![FlatVariable::public(0)],
+                },
+                private: vec![true],
+            };
+
+            assert_eq!(prog.constraint_count(), 2);
+            assert_eq!(
+                prog.statistics(),
+                IrStats {
+                    constraints: 2,
+                    variables: 3,
+                    public_inputs: 1
+                }
+            );
+        }
+
+        #[test]
+        fn constant_one() {
+            // def main(_0, private _1) -> (1):
+            //      _0 * _1 == _2
+            //      (1 * _2 + 1 * ~one) * 1 == ~out_0
+            let prog: Prog<Bn128Field> = Prog {
+                main: Function {
+                    id: "main".into(),
+                    statements: vec![
+                        Statement::constraint(
+                            QuadComb::from_linear_combinations(
+                                FlatVariable::new(0).into(),
+                                FlatVariable::new(1).into(),
+                            ),
+                            FlatVariable::new(2),
+                        ),
+                        Statement::definition(
+                            FlatVariable::public(0),
+                            LinComb(vec![
+                                (FlatVariable::new(2), Bn128Field::from(1)),
+                                (FlatVariable::one(), Bn128Field::from(1)),
+                            ]),
+                        ),
+                    ],
+                    arguments: vec![FlatVariable::new(0), FlatVariable::new(1)],
+                    returns: vec![FlatVariable::public(0)],
+                },
+                private: vec![false, true],
+            };
+
+            assert_eq!(
+                prog.statistics(),
+                IrStats {
+                    constraints: 2,
+                    variables: 4,
+                    public_inputs: 2
+                }
+            );
+        }
+    }
 }
@@ -25,3 +25,7 @@ pub mod flat_absy;
 pub mod ir;
 pub mod proof_system;
 pub mod typed_absy;
+
+pub use static_analysis::{
+    reduce_function, reduce_program_partial, InlinePolicy, ReduceOutcome, ReducerConfig,
+};
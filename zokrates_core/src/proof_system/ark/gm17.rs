@@ -250,7 +250,7 @@ mod tests {
     use crate::ir::{Function, Interpreter, Prog, Statement};
 
     use super::*;
-    use zokrates_field::{Bls12_377Field, Bw6_761Field};
+    use zokrates_field::{Bls12_377Field, Bls12_381Field, Bw6_761Field};
 
     #[test]
     fn verify_bls12_377_field() {
@@ -281,6 +281,35 @@ mod tests {
         assert!(ans);
     }
 
+    #[test]
+    fn verify_bls12_381_field() {
+        let program: Prog<Bls12_381Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::Constraint(
+                    FlatVariable::new(0).into(),
+                    FlatVariable::public(0).into(),
+                )],
+            },
+            private: vec![false],
+        };
+
+        let keypair = <Ark as Backend<Bls12_381Field, GM17>>::setup(program.clone());
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(&program, &[Bls12_381Field::from(42)])
+            .unwrap();
+
+        let proof =
+            <Ark as Backend<Bls12_381Field, GM17>>::generate_proof(program, witness, keypair.pk);
+        let ans = <Ark as Backend<Bls12_381Field, GM17>>::verify(keypair.vk, proof);
+
+        assert!(ans);
+    }
+
     #[test]
     fn verify_bw6_761_field() {
         let program: Prog<Bw6_761Field> = Prog {
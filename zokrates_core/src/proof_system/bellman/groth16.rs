@@ -104,6 +104,89 @@ impl<T: Field + BellmanFieldExtensions> Backend<T, G16> for Bellman {
 
         verify_proof(&pvk, &bellman_proof, &public_inputs).unwrap()
     }
+
+    fn batch_verify(
+        vk: <G16 as Scheme<T>>::VerificationKey,
+        proofs: Vec<Proof<<G16 as Scheme<T>>::ProofPoints>>,
+    ) -> bool {
+        // each proof is checked with e(A, B) == e(alpha, beta) * e(inputs, gamma) * e(C, delta)
+        // we check a random linear combination of these equations, which holds for all proofs at once
+        // except with negligible probability if one of them is invalid, using a single final exponentiation
+        use pairing::ff::{Field as PairingField, PrimeField, ScalarEngine};
+        use pairing::CurveProjective;
+        use rand_0_4::Rng;
+
+        type G1<T> = <<T as BellmanFieldExtensions>::BellmanEngine as Engine>::G1;
+
+        let alpha = serialization::to_g1::<T>(vk.alpha);
+        let beta = serialization::to_g2::<T>(vk.beta);
+        let mut neg_gamma = serialization::to_g2::<T>(vk.gamma);
+        neg_gamma.negate();
+        let mut neg_delta = serialization::to_g2::<T>(vk.delta);
+        neg_delta.negate();
+        let ic: Vec<_> = vk
+            .gamma_abc
+            .into_iter()
+            .map(serialization::to_g1::<T>)
+            .collect();
+
+        let mut rng = rand_0_4::thread_rng();
+
+        let mut r_sum = <T::BellmanEngine as ScalarEngine>::Fr::zero();
+        let mut inputs_acc = G1::<T>::zero();
+        let mut c_acc = G1::<T>::zero();
+        let mut prepared = vec![];
+
+        for proof in proofs {
+            let r: <T::BellmanEngine as ScalarEngine>::Fr = rng.gen();
+
+            let public_inputs: Vec<_> = proof
+                .inputs
+                .iter()
+                .map(|s| {
+                    T::try_from_str(s.trim_start_matches("0x"), 16)
+                        .unwrap()
+                        .into_bellman()
+                })
+                .collect();
+
+            if public_inputs.len() + 1 != ic.len() {
+                return false;
+            }
+
+            let mut inputs = ic[0].into_projective();
+            for (i, b) in public_inputs.iter().zip(ic.iter().skip(1)) {
+                inputs.add_assign(&b.mul(i.into_repr()));
+            }
+            inputs.mul_assign(r.into_repr());
+            inputs_acc.add_assign(&inputs);
+
+            c_acc.add_assign(&serialization::to_g1::<T>(proof.proof.c).mul(r.into_repr()));
+
+            let a = serialization::to_g1::<T>(proof.proof.a)
+                .mul(r.into_repr())
+                .into_affine();
+            let b = serialization::to_g2::<T>(proof.proof.b);
+            prepared.push((a.prepare(), b.prepare()));
+
+            r_sum.add_assign(&r);
+        }
+
+        let mut neg_alpha = alpha.mul(r_sum.into_repr()).into_affine();
+        neg_alpha.negate();
+
+        prepared.push((neg_alpha.prepare(), beta.prepare()));
+        prepared.push((inputs_acc.into_affine().prepare(), neg_gamma.prepare()));
+        prepared.push((c_acc.into_affine().prepare(), neg_delta.prepare()));
+
+        let pairs: Vec<_> = prepared.iter().map(|(a, b)| (a, b)).collect();
+
+        <T::BellmanEngine as Engine>::final_exponentiation(
+            &<T::BellmanEngine as Engine>::miller_loop(&pairs),
+        )
+        .map(|e| e == <<T::BellmanEngine as Engine>::Fqk as PairingField>::one())
+        .unwrap_or(false)
+    }
 }
 
 mod serialization {
@@ -165,4 +248,60 @@ mod tests {
 
         assert!(ans);
     }
+
+    #[test]
+    fn batch_verify() {
+        let program: Prog<Bn128Field> = Prog {
+            main: Function {
+                id: String::from("main"),
+                arguments: vec![FlatVariable::new(0)],
+                returns: vec![FlatVariable::public(0)],
+                statements: vec![Statement::Constraint(
+                    FlatVariable::new(0).into(),
+                    FlatVariable::public(0).into(),
+                )],
+            },
+            private: vec![false],
+        };
+
+        let keypair = <Bellman as Backend<Bn128Field, G16>>::setup(program.clone());
+        let interpreter = Interpreter::default();
+
+        let proofs = |values: Vec<usize>| {
+            values
+                .into_iter()
+                .map(|v| {
+                    let witness = interpreter
+                        .execute(&program, &[Bn128Field::from(v)])
+                        .unwrap();
+                    <Bellman as Backend<Bn128Field, G16>>::generate_proof(
+                        program.clone(),
+                        witness,
+                        keypair.pk.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // all valid
+        assert!(<Bellman as Backend<Bn128Field, G16>>::batch_verify(
+            keypair.vk.clone(),
+            proofs(vec![42, 43, 44])
+        ));
+
+        // no proof
+        assert!(<Bellman as Backend<Bn128Field, G16>>::batch_verify(
+            keypair.vk.clone(),
+            vec![]
+        ));
+
+        // one proof with a wrong public input
+        let mut invalid = proofs(vec![42, 43, 44]);
+        invalid[1].inputs = proofs(vec![45]).pop().unwrap().inputs;
+
+        assert!(!<Bellman as Backend<Bn128Field, G16>>::batch_verify(
+            keypair.vk.clone(),
+            invalid
+        ));
+    }
 }
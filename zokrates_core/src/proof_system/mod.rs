@@ -7,13 +7,17 @@ pub mod libsnark;
 
 mod scheme;
 mod solidity;
+mod vyper;
 
 pub use self::scheme::*;
 pub use self::solidity::*;
+pub use self::vyper::*;
 
 use crate::ir;
+use bincode::Infinite;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 use zokrates_field::Field;
 
 #[derive(Serialize)]
@@ -28,32 +32,77 @@ impl<V: Serialize + DeserializeOwned> SetupKeypair<V> {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Proof<T> {
     pub proof: T,
     pub inputs: Vec<String>,
 }
 
+/// The formats a proof can be exported to. `Hex`, `Base64` and `Bin` are encodings of the same binary representation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProofFormat {
+    Json,
+    Hex,
+    Base64,
+    Bin,
+}
+
+impl TryFrom<&str> for ProofFormat {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<ProofFormat, Self::Error> {
+        match s {
+            "json" => Ok(ProofFormat::Json),
+            "hex" => Ok(ProofFormat::Hex),
+            "base64" => Ok(ProofFormat::Base64),
+            "bin" => Ok(ProofFormat::Bin),
+            _ => Err(format!("Unknown proof format {}", s)),
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl<T: Serialize + DeserializeOwned> Proof<T> {
     fn new(proof: T, inputs: Vec<String>) -> Self {
         Proof { proof, inputs }
     }
+
+    pub fn to_bytes(&self, format: ProofFormat) -> Vec<u8> {
+        match format {
+            ProofFormat::Json => serde_json::to_string_pretty(self).unwrap().into_bytes(),
+            ProofFormat::Bin => bincode::serialize(self, Infinite).unwrap(),
+            ProofFormat::Hex => hex::encode(self.to_bytes(ProofFormat::Bin)).into_bytes(),
+            ProofFormat::Base64 => base64::encode(self.to_bytes(ProofFormat::Bin)).into_bytes(),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8], format: ProofFormat) -> Result<Self, String> {
+        match format {
+            ProofFormat::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            ProofFormat::Bin => bincode::deserialize(bytes).map_err(|e| e.to_string()),
+            ProofFormat::Hex => hex::decode(String::from_utf8_lossy(bytes).trim())
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| Self::from_bytes(&bytes, ProofFormat::Bin)),
+            ProofFormat::Base64 => base64::decode(String::from_utf8_lossy(bytes).trim())
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| Self::from_bytes(&bytes, ProofFormat::Bin)),
+        }
+    }
 }
 
 pub type Fr = String;
 pub type Fq = String;
 pub type Fq2 = (String, String);
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct G1Affine(Fq, Fq);
 
 // When G2 is defined on Fq2 field
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct G2Affine(Fq2, Fq2);
 
 // When G2 is defined on a Fq field (BW6_761 curve)
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct G2AffineFq(Fq, Fq);
 
 impl ToString for G1Affine {
@@ -89,4 +138,60 @@ pub trait Backend<T: Field, S: Scheme<T>> {
     ) -> Proof<S::ProofPoints>;
 
     fn verify(vk: S::VerificationKey, proof: Proof<S::ProofPoints>) -> bool;
+
+    /// Verify proofs sharing a verification key, returning `true` only if all of them are valid
+    fn batch_verify(vk: S::VerificationKey, proofs: Vec<Proof<S::ProofPoints>>) -> bool {
+        proofs
+            .into_iter()
+            .all(|proof| Self::verify(vk.clone(), proof))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn g1(x: &str, y: &str) -> G1Affine {
+        G1Affine(x.into(), y.into())
+    }
+
+    fn round_trip<T: Serialize + DeserializeOwned + std::fmt::Debug + PartialEq>(proof: Proof<T>) {
+        for format in &["json", "hex", "base64", "bin"] {
+            let format = ProofFormat::try_from(*format).unwrap();
+            let bytes = proof.to_bytes(format);
+            assert_eq!(Proof::from_bytes(&bytes, format).unwrap(), proof);
+        }
+    }
+
+    #[test]
+    fn g16_round_trip() {
+        round_trip(Proof::new(
+            groth16::ProofPoints {
+                a: g1("0x01", "0x02"),
+                b: G2Affine(
+                    ("0x03".into(), "0x04".into()),
+                    ("0x05".into(), "0x06".into()),
+                ),
+                c: g1("0x07", "0x08"),
+            },
+            vec!["0x09".into()],
+        ));
+    }
+
+    #[test]
+    fn gm17_round_trip() {
+        round_trip(Proof::new(
+            gm17::ProofPoints {
+                a: g1("0x01", "0x02"),
+                b: G2AffineFq("0x03".into(), "0x04".into()),
+                c: g1("0x05", "0x06"),
+            },
+            vec![],
+        ));
+    }
+
+    #[test]
+    fn unknown_format() {
+        assert!(ProofFormat::try_from("xml").is_err());
+    }
 }
@@ -1,6 +1,7 @@
 use crate::proof_system::scheme::Scheme;
 use crate::proof_system::solidity::{
-    SolidityAbi, SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
+    SolidityAbi, SolidityInputs, SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB,
+    SOLIDITY_PAIRING_LIB_V2,
 };
 use crate::proof_system::{
     G1Affine, G2Affine, G2AffineFq, SolidityCompatibleField, SolidityCompatibleScheme,
@@ -17,14 +18,14 @@ impl NotBw6_761Field for Bn128Field {}
 #[allow(clippy::upper_case_acronyms)]
 pub struct GM17;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ProofPoints<G1, G2> {
     pub a: G1,
     pub b: G2,
     pub c: G1,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct VerificationKey<G1, G2> {
     pub h: G2,
     pub g_alpha: G1,
@@ -48,6 +49,7 @@ impl<T: SolidityCompatibleField + NotBw6_761Field> SolidityCompatibleScheme<T> f
     fn export_solidity_verifier(
         vk: <GM17 as Scheme<T>>::VerificationKey,
         abi: SolidityAbi,
+        inputs: SolidityInputs,
     ) -> String {
         let (mut template_text, solidity_pairing_lib) = match abi {
             SolidityAbi::V1 => (
@@ -100,30 +102,19 @@ impl<T: SolidityCompatibleField + NotBw6_761Field> SolidityCompatibleScheme<T> f
             )
             .into_owned();
 
-        // feed input values only if there are any
-        template_text = if query_count > 1 {
-            input_loop.replace(
+        template_text = input_loop
+            .replace(
                 template_text.as_str(),
-                r#"
-        for(uint i = 0; i < input.length; i++){
-            inputValues[i] = input[i];
-        }"#,
+                inputs.copy(query_count - 1).as_str(),
             )
-        } else {
-            input_loop.replace(template_text.as_str(), "")
-        }
-        .to_string();
+            .into_owned();
 
-        // take input values as argument only if there are any
-        template_text = if query_count > 1 {
-            input_argument.replace(
+        template_text = input_argument
+            .replace(
                 template_text.as_str(),
-                format!(", uint[{}] memory input", query_count - 1).as_str(),
+                inputs.argument(query_count - 1).as_str(),
             )
-        } else {
-            input_argument.replace(template_text.as_str(), "")
-        }
-        .to_string();
+            .into_owned();
 
         let mut query_repeat_text = String::new();
         for (i, g1) in vk.query.iter().enumerate() {
@@ -1,22 +1,26 @@
 use crate::proof_system::scheme::Scheme;
 use crate::proof_system::solidity::{
-    SolidityAbi, SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
+    SolidityAbi, SolidityInputs, SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB,
+    SOLIDITY_PAIRING_LIB_V2,
+};
+use crate::proof_system::vyper::{to_decimal, VYPER_PAIRING_LIB};
+use crate::proof_system::{
+    G1Affine, G2Affine, SolidityCompatibleField, SolidityCompatibleScheme, VyperCompatibleScheme,
 };
-use crate::proof_system::{G1Affine, G2Affine, SolidityCompatibleField, SolidityCompatibleScheme};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use zokrates_field::Field;
 
 pub struct G16;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ProofPoints<G1, G2> {
     pub a: G1,
     pub b: G2,
     pub c: G1,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct VerificationKey<G1, G2> {
     pub alpha: G1,
     pub beta: G2,
@@ -34,6 +38,7 @@ impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for G16 {
     fn export_solidity_verifier(
         vk: <G16 as Scheme<T>>::VerificationKey,
         abi: SolidityAbi,
+        inputs: SolidityInputs,
     ) -> String {
         let (mut template_text, solidity_pairing_lib) = match abi {
             SolidityAbi::V1 => (
@@ -84,30 +89,19 @@ impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for G16 {
             )
             .into_owned();
 
-        // feed input values only if there are any
-        template_text = if gamma_abc_count > 1 {
-            input_loop.replace(
+        template_text = input_loop
+            .replace(
                 template_text.as_str(),
-                r#"
-        for(uint i = 0; i < input.length; i++){
-            inputValues[i] = input[i];
-        }"#,
+                inputs.copy(gamma_abc_count - 1).as_str(),
             )
-        } else {
-            input_loop.replace(template_text.as_str(), "")
-        }
-        .to_string();
+            .into_owned();
 
-        // take input values as argument only if there are any
-        template_text = if gamma_abc_count > 1 {
-            input_argument.replace(
+        template_text = input_argument
+            .replace(
                 template_text.as_str(),
-                format!(", uint[{}] memory input", gamma_abc_count - 1).as_str(),
+                inputs.argument(gamma_abc_count - 1).as_str(),
             )
-        } else {
-            input_argument.replace(template_text.as_str(), "")
-        }
-        .to_string();
+            .into_owned();
 
         let mut gamma_abc_repeat_text = String::new();
         for (i, g1) in vk.gamma_abc.iter().enumerate() {
@@ -138,6 +132,84 @@ impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for G16 {
     }
 }
 
+impl<T: SolidityCompatibleField> VyperCompatibleScheme<T> for G16 {
+    fn export_vyper_verifier(vk: <G16 as Scheme<T>>::VerificationKey) -> String {
+        let mut template_text = String::from(VYPER_CONTRACT_TEMPLATE);
+
+        let vk_regex = Regex::new(r#"(<%vk_[^i%]*%>)"#).unwrap();
+        let vk_gamma_abc_len_regex = Regex::new(r#"(<%vk_gamma_abc_length%>)"#).unwrap();
+        let vk_gamma_abc_repeat_regex = Regex::new(r#"(<%vk_gamma_abc_pts%>)"#).unwrap();
+        let input_loop = Regex::new(r#"(<%input_loop%>)"#).unwrap();
+        let input_argument = Regex::new(r#"(<%input_argument%>)"#).unwrap();
+
+        template_text = vk_regex
+            .replace(template_text.as_str(), vk.alpha.to_string().as_str())
+            .into_owned();
+
+        template_text = vk_regex
+            .replace(template_text.as_str(), vk.beta.to_string().as_str())
+            .into_owned();
+
+        template_text = vk_regex
+            .replace(template_text.as_str(), vk.gamma.to_string().as_str())
+            .into_owned();
+
+        template_text = vk_regex
+            .replace(template_text.as_str(), vk.delta.to_string().as_str())
+            .into_owned();
+
+        let gamma_abc_count: usize = vk.gamma_abc.len();
+        template_text = vk_gamma_abc_len_regex
+            .replace(
+                template_text.as_str(),
+                format!("{}", gamma_abc_count).as_str(),
+            )
+            .into_owned();
+
+        // feed input values only if there are any
+        template_text = if gamma_abc_count > 1 {
+            input_loop.replace(
+                template_text.as_str(),
+                format!(
+                    r#"
+    for i in range({}):
+        assert input[i] < SNARK_SCALAR_FIELD
+        vk_x = ecadd(vk_x, ecmul(vk_gamma_abc[i + 1], input[i]))"#,
+                    gamma_abc_count - 1
+                )
+                .as_str(),
+            )
+        } else {
+            input_loop.replace(template_text.as_str(), "")
+        }
+        .to_string();
+
+        // take input values as argument only if there are any
+        template_text = if gamma_abc_count > 1 {
+            input_argument.replace(
+                template_text.as_str(),
+                format!(", input: uint256[{}]", gamma_abc_count - 1).as_str(),
+            )
+        } else {
+            input_argument.replace(template_text.as_str(), "")
+        }
+        .to_string();
+
+        let gamma_abc_repeat_text = vk
+            .gamma_abc
+            .iter()
+            .map(|g1| format!("[{}]", g1.to_string()))
+            .collect::<Vec<_>>()
+            .join(",\n        ");
+
+        template_text = vk_gamma_abc_repeat_regex
+            .replace(template_text.as_str(), gamma_abc_repeat_text.as_str())
+            .into_owned();
+
+        to_decimal(&format!("{}{}", VYPER_PAIRING_LIB, template_text))
+    }
+}
+
 const CONTRACT_TEMPLATE_V2: &str = r#"
 contract Verifier {
     using Pairing for *;
@@ -253,3 +325,163 @@ contract Verifier {
     }
 }
 "#;
+
+const VYPER_CONTRACT_TEMPLATE: &str = r#"
+
+@external
+@view
+def verifyTx(
+    a: uint256[2],
+    b: uint256[2][2],
+    c: uint256[2]<%input_argument%>
+) -> bool:
+    vk_alpha: uint256[2] = [<%vk_alpha%>]
+    vk_beta: uint256[2][2] = [<%vk_beta%>]
+    vk_gamma: uint256[2][2] = [<%vk_gamma%>]
+    vk_delta: uint256[2][2] = [<%vk_delta%>]
+    vk_gamma_abc: uint256[2][<%vk_gamma_abc_length%>] = [
+        <%vk_gamma_abc_pts%>
+    ]
+    # Compute the linear combination vk_x
+    vk_x: uint256[2] = [0, 0]<%input_loop%>
+    vk_x = ecadd(vk_x, vk_gamma_abc[0])
+    return self.pairing_prod_4(
+        a, b,
+        self.negate(vk_x), vk_gamma,
+        self.negate(c), vk_delta,
+        self.negate(vk_alpha), vk_beta
+    )
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    // a verification key with distinct small coordinates, so that they can be traced in the output
+    fn verification_key(inputs: usize) -> VerificationKey<G1Affine, G2Affine> {
+        let fq = |v: usize| format!("0x{:064x}", v);
+        let g1 = |v: usize| G1Affine(fq(v), fq(v + 1));
+        let g2 = |v: usize| G2Affine((fq(v), fq(v + 1)), (fq(v + 2), fq(v + 3)));
+
+        VerificationKey {
+            alpha: g1(1),
+            beta: g2(10),
+            gamma: g2(20),
+            delta: g2(30),
+            gamma_abc: (0..inputs + 1).map(|i| g1(100 + 2 * i)).collect(),
+        }
+    }
+
+    fn export(inputs: usize, abi: SolidityAbi, mode: SolidityInputs) -> String {
+        <G16 as SolidityCompatibleScheme<Bn128Field>>::export_solidity_verifier(
+            verification_key(inputs),
+            abi,
+            mode,
+        )
+    }
+
+    #[test]
+    fn fixed_inputs() {
+        for &inputs in &[1, 8, 20] {
+            let verifier = export(inputs, SolidityAbi::V1, SolidityInputs::Fixed);
+
+            assert!(verifier.contains(&format!(
+                "uint[2] memory c, uint[{}] memory input\n        ) public view returns (bool r) {{",
+                inputs
+            )));
+            assert!(!verifier.contains("require(input.length =="));
+        }
+    }
+
+    #[test]
+    fn dynamic_inputs() {
+        for &inputs in &[1, 8, 20] {
+            for (abi, argument) in vec![
+                (SolidityAbi::V1, "uint[2] memory c, uint[] memory input"),
+                (SolidityAbi::V2, "Proof memory proof, uint[] memory input"),
+            ] {
+                let verifier = export(inputs, abi, SolidityInputs::Dynamic);
+
+                assert!(verifier.contains(&format!(
+                    "{}\n        ) public view returns (bool r) {{",
+                    argument
+                )));
+                assert!(verifier.contains(&format!(
+                    "uint[] memory inputValues = new uint[]({});\n        \n        require(input.length == {});\n        for(uint i = 0; i < input.length; i++){{\n            inputValues[i] = input[i];\n        }}",
+                    inputs, inputs
+                )));
+            }
+        }
+    }
+
+    #[test]
+    fn vyper_verifier() {
+        let verifier =
+            <G16 as VyperCompatibleScheme<Bn128Field>>::export_vyper_verifier(verification_key(1));
+
+        let expected = r#"
+@external
+@view
+def verifyTx(
+    a: uint256[2],
+    b: uint256[2][2],
+    c: uint256[2], input: uint256[1]
+) -> bool:
+    vk_alpha: uint256[2] = [1, 2]
+    vk_beta: uint256[2][2] = [[10, 11], [12, 13]]
+    vk_gamma: uint256[2][2] = [[20, 21], [22, 23]]
+    vk_delta: uint256[2][2] = [[30, 31], [32, 33]]
+    vk_gamma_abc: uint256[2][2] = [
+        [100, 101],
+        [102, 103]
+    ]
+    # Compute the linear combination vk_x
+    vk_x: uint256[2] = [0, 0]
+    for i in range(1):
+        assert input[i] < SNARK_SCALAR_FIELD
+        vk_x = ecadd(vk_x, ecmul(vk_gamma_abc[i + 1], input[i]))
+    vk_x = ecadd(vk_x, vk_gamma_abc[0])
+    return self.pairing_prod_4(
+        a, b,
+        self.negate(vk_x), vk_gamma,
+        self.negate(c), vk_delta,
+        self.negate(vk_alpha), vk_beta
+    )
+"#;
+
+        assert!(verifier.starts_with(VYPER_PAIRING_LIB));
+        assert!(verifier.ends_with(expected));
+    }
+
+    #[test]
+    fn vyper_verifier_matches_solidity() {
+        for &inputs in &[0, 1, 8] {
+            let solidity = export(inputs, SolidityAbi::V2, SolidityInputs::Fixed);
+            let vyper = <G16 as VyperCompatibleScheme<Bn128Field>>::export_vyper_verifier(
+                verification_key(inputs),
+            );
+
+            // the coordinates embedded in the solidity verifier, in order
+            let solidity_points: Vec<_> = Regex::new(r"uint256\((0x[0-9a-f]{64})\)")
+                .unwrap()
+                .captures_iter(&solidity)
+                .map(|c| to_decimal(&c[1]))
+                .collect();
+
+            // the coordinates embedded in the vyper verifier, in order
+            let vk = &vyper[vyper.find("vk_alpha").unwrap()..vyper.find("# Compute").unwrap()];
+            let vk = Regex::new(r"uint256(\[\d+\])+")
+                .unwrap()
+                .replace_all(vk, "");
+            let vyper_points: Vec<_> = Regex::new(r"\d+")
+                .unwrap()
+                .find_iter(&vk)
+                .map(|m| m.as_str().to_string())
+                .collect();
+
+            assert_eq!(solidity_points.len(), 2 + 3 * 4 + 2 * (inputs + 1));
+            assert_eq!(solidity_points, vyper_points);
+        }
+    }
+}
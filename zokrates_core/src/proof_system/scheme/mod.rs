@@ -11,6 +11,6 @@ pub use self::groth16::G16;
 pub use self::pghr13::PGHR13;
 
 pub trait Scheme<T: Field> {
-    type VerificationKey: Serialize + DeserializeOwned;
+    type VerificationKey: Serialize + DeserializeOwned + Clone;
     type ProofPoints: Serialize + DeserializeOwned;
 }
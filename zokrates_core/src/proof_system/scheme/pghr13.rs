@@ -1,6 +1,7 @@
 use crate::proof_system::scheme::Scheme;
 use crate::proof_system::solidity::{
-    SolidityAbi, SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB, SOLIDITY_PAIRING_LIB_V2,
+    SolidityAbi, SolidityInputs, SOLIDITY_G2_ADDITION_LIB, SOLIDITY_PAIRING_LIB,
+    SOLIDITY_PAIRING_LIB_V2,
 };
 use crate::proof_system::{G1Affine, G2Affine, SolidityCompatibleField, SolidityCompatibleScheme};
 use regex::Regex;
@@ -10,7 +11,7 @@ use zokrates_field::Field;
 #[allow(clippy::upper_case_acronyms)]
 pub struct PGHR13;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ProofPoints<G1, G2> {
     pub a: G1,
     pub a_p: G1,
@@ -22,7 +23,7 @@ pub struct ProofPoints<G1, G2> {
     pub k: G1,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct VerificationKey<G1, G2> {
     pub a: G2,
     pub b: G1,
@@ -43,6 +44,7 @@ impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for PGHR13 {
     fn export_solidity_verifier(
         vk: <PGHR13 as Scheme<T>>::VerificationKey,
         abi: SolidityAbi,
+        inputs: SolidityInputs,
     ) -> String {
         let (mut template_text, solidity_pairing_lib) = match abi {
             SolidityAbi::V1 => (
@@ -100,30 +102,16 @@ impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for PGHR13 {
             .replace(template_text.as_str(), format!("{}", ic_count - 1).as_str())
             .into_owned();
 
-        // feed input values only if there are any
-        template_text = if ic_count > 1 {
-            input_loop.replace(
-                template_text.as_str(),
-                r#"
-        for(uint i = 0; i < input.length; i++){
-            inputValues[i] = input[i];
-        }"#,
-            )
-        } else {
-            input_loop.replace(template_text.as_str(), "")
-        }
-        .to_string();
+        template_text = input_loop
+            .replace(template_text.as_str(), inputs.copy(ic_count - 1).as_str())
+            .into_owned();
 
-        // take input values as argument only if there are any
-        template_text = if ic_count > 1 {
-            input_argument.replace(
+        template_text = input_argument
+            .replace(
                 template_text.as_str(),
-                format!(", uint[{}] memory input", ic_count - 1).as_str(),
+                inputs.argument(ic_count - 1).as_str(),
             )
-        } else {
-            input_argument.replace(template_text.as_str(), "")
-        }
-        .to_string();
+            .into_owned();
 
         let mut ic_repeat_text = String::new();
         for (i, g1) in vk.ic.iter().enumerate() {
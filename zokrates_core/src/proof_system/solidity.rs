@@ -5,7 +5,11 @@ pub trait SolidityCompatibleField: Field {}
 impl SolidityCompatibleField for Bn128Field {}
 
 pub trait SolidityCompatibleScheme<T: SolidityCompatibleField>: Scheme<T> {
-    fn export_solidity_verifier(vk: Self::VerificationKey, abi: SolidityAbi) -> String;
+    fn export_solidity_verifier(
+        vk: Self::VerificationKey,
+        abi: SolidityAbi,
+        inputs: SolidityInputs,
+    ) -> String;
 }
 
 pub enum SolidityAbi {
@@ -23,6 +27,43 @@ impl SolidityAbi {
     }
 }
 
+/// How the public inputs are passed to `verifyTx` in the exported verifier
+pub enum SolidityInputs {
+    /// A fixed-size array `uint[N]`, where `N` is the number of public inputs of the circuit
+    Fixed,
+    /// A dynamic array `uint[]`, whose length is checked against the number of public inputs at runtime
+    Dynamic,
+}
+
+impl SolidityInputs {
+    // the declaration of the `input` argument of `verifyTx`, given the number of public inputs
+    pub(crate) fn argument(&self, count: usize) -> String {
+        match (self, count) {
+            (SolidityInputs::Fixed, 0) => String::new(),
+            (SolidityInputs::Fixed, count) => format!(", uint[{}] memory input", count),
+            (SolidityInputs::Dynamic, _) => String::from(", uint[] memory input"),
+        }
+    }
+
+    // the copy of the `input` argument of `verifyTx` into `inputValues`, given the number of public inputs
+    pub(crate) fn copy(&self, count: usize) -> String {
+        let copy = r#"
+        for(uint i = 0; i < input.length; i++){
+            inputValues[i] = input[i];
+        }"#;
+
+        match (self, count) {
+            (SolidityInputs::Fixed, 0) => String::new(),
+            (SolidityInputs::Fixed, _) => String::from(copy),
+            (SolidityInputs::Dynamic, count) => format!(
+                r#"
+        require(input.length == {});{}"#,
+                count, copy
+            ),
+        }
+    }
+}
+
 pub const SOLIDITY_G2_ADDITION_LIB: &str = r#"// SPDX-License-Identifier: LGPL-3.0-only
 // This file is LGPL3 Licensed
 pragma solidity ^0.6.1;
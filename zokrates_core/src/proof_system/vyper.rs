@@ -0,0 +1,68 @@
+use crate::proof_system::{Scheme, SolidityCompatibleField};
+use num_bigint::BigUint;
+use regex::{Captures, Regex};
+
+pub trait VyperCompatibleScheme<T: SolidityCompatibleField>: Scheme<T> {
+    fn export_vyper_verifier(vk: Self::VerificationKey) -> String;
+}
+
+// Vyper reads long hexadecimal literals as bytes, so field elements are written in decimal
+pub(crate) fn to_decimal(text: &str) -> String {
+    let re = Regex::new(r"0[xX](?P<v>[0-9a-fA-F]{64})").unwrap();
+    re.replace_all(text, |caps: &Captures| {
+        BigUint::parse_bytes(caps["v"].as_bytes(), 16)
+            .unwrap()
+            .to_str_radix(10)
+    })
+    .into_owned()
+}
+
+pub const VYPER_PAIRING_LIB: &str = r#"# This file is MIT Licensed.
+#
+# Port of the Solidity pairing library by Christian Reitwiessner, using the same precompiled contracts
+
+# The prime q in the base field F_q for G1
+PRIME_Q: constant(uint256) = 21888242871839275222246405745257275088696311157297823662689037894645226208583
+SNARK_SCALAR_FIELD: constant(uint256) = 21888242871839275222246405745257275088548364400416034343698204186575808495617
+PAIRING_PRECOMPILE: constant(address) = 0x0000000000000000000000000000000000000008
+
+
+# @return the negation of p, i.e. p.addition(p.negate()) should be zero.
+@internal
+@pure
+def negate(p: uint256[2]) -> uint256[2]:
+    if p[0] == 0 and p[1] == 0:
+        return [0, 0]
+    return [p[0], PRIME_Q - (p[1] % PRIME_Q)]
+
+
+# @return whether the product of the pairings of the four pairs of points is one.
+@internal
+@view
+def pairing_prod_4(
+    a1: uint256[2], a2: uint256[2][2],
+    b1: uint256[2], b2: uint256[2][2],
+    c1: uint256[2], c2: uint256[2][2],
+    d1: uint256[2], d2: uint256[2][2]
+) -> bool:
+    out: Bytes[32] = raw_call(
+        PAIRING_PRECOMPILE,
+        concat(
+            convert(a1[0], bytes32), convert(a1[1], bytes32),
+            convert(a2[0][1], bytes32), convert(a2[0][0], bytes32),
+            convert(a2[1][1], bytes32), convert(a2[1][0], bytes32),
+            convert(b1[0], bytes32), convert(b1[1], bytes32),
+            convert(b2[0][1], bytes32), convert(b2[0][0], bytes32),
+            convert(b2[1][1], bytes32), convert(b2[1][0], bytes32),
+            convert(c1[0], bytes32), convert(c1[1], bytes32),
+            convert(c2[0][1], bytes32), convert(c2[0][0], bytes32),
+            convert(c2[1][1], bytes32), convert(c2[1][0], bytes32),
+            convert(d1[0], bytes32), convert(d1[1], bytes32),
+            convert(d2[0][1], bytes32), convert(d2[0][0], bytes32),
+            convert(d2[1][1], bytes32), convert(d2[1][0], bytes32)
+        ),
+        max_outsize=32,
+        is_static_call=True
+    )
+    return convert(out, uint256) != 0
+"#;
@@ -1,10 +1,33 @@
+//! Module containing the check of array accesses at constant indices
+//!
+//! Once propagation has resolved indices and array sizes to constants, an access out of the bounds of its array
+//! is reported as a compile error rather than left to fail during witness generation.
+
 use crate::typed_absy::result_folder::*;
 use crate::typed_absy::*;
+use std::fmt;
 use zokrates_field::Field;
 
 pub struct BoundsChecker;
 
-pub type Error = String;
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    IndexOutOfBounds { index: u128, len: u128 },
+    Slice(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::IndexOutOfBounds { index, len } => write!(
+                f,
+                "Out of bounds access: index {} is out of bounds for array of size {}",
+                index, len
+            ),
+            Error::Slice(s) => write!(f, "{}", s),
+        }
+    }
+}
 
 impl BoundsChecker {
     pub fn check<T: Field>(p: TypedProgram<T>) -> Result<TypedProgram<T>, Error> {
@@ -33,18 +56,18 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for BoundsChecker {
                         UExpressionInner::Value(to),
                     ) => {
                         if from > to {
-                            return Err(format!(
+                            return Err(Error::Slice(format!(
                                 "Slice is created from an invalid range {}..{}",
                                 from, to
-                            ));
+                            )));
                         }
 
                         if from > size {
-                            return Err(format!("Lower bound {} of slice {}[{}..{}] is out of bounds for array of size {}", from, array, from, to, size));
+                            return Err(Error::Slice(format!("Lower bound {} of slice {}[{}..{}] is out of bounds for array of size {}", from, array, from, to, size)));
                         }
 
                         if to > size {
-                            return Err(format!("Upper bound {} of slice {}[{}..{}] is out of bounds for array of size {}", to, array, from, to, size));
+                            return Err(Error::Slice(format!("Upper bound {} of slice {}[{}..{}] is out of bounds for array of size {}", to, array, from, to, size)));
                         }
                     }
                     _ => unreachable!(),
@@ -66,20 +89,65 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for BoundsChecker {
         let array = self.fold_array_expression(*select.array)?;
         let index = self.fold_uint_expression(*select.index)?;
 
-        match (array.ty().size.as_inner(), index.as_inner()) {
-            (UExpressionInner::Value(size), UExpressionInner::Value(index)) => {
-                if index >= size {
-                    return Err(format!(
-                        "Out of bounds access: {}[{}] but {} is of size {}",
-                        array, index, array, size
-                    ));
-                }
+        // only accesses at a constant index in an array of constant size can be checked
+        if let (UExpressionInner::Value(size), UExpressionInner::Value(index)) =
+            (array.ty().size.as_inner(), index.as_inner())
+        {
+            if index >= size {
+                return Err(Error::IndexOutOfBounds {
+                    index: *index,
+                    len: *size,
+                });
             }
-            _ => unreachable!(),
-        };
+        }
 
         Ok(SelectOrExpression::Select(SelectExpression::new(
             array, index,
         )))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    fn select(index: u32) -> FieldElementExpression<'static, Bn128Field> {
+        FieldElementExpression::select(
+            ArrayExpressionInner::Identifier("a".into()).annotate(Type::FieldElement, 3u32),
+            index,
+        )
+    }
+
+    #[test]
+    fn in_bounds() {
+        // a[2] with `field[3] a`
+        assert_eq!(
+            BoundsChecker.fold_field_expression(select(2)),
+            Ok(select(2))
+        );
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        // a[3] with `field[3] a`
+        let error = BoundsChecker.fold_field_expression(select(3)).unwrap_err();
+
+        assert_eq!(error, Error::IndexOutOfBounds { index: 3, len: 3 });
+        assert_eq!(
+            error.to_string(),
+            "Out of bounds access: index 3 is out of bounds for array of size 3"
+        );
+    }
+
+    #[test]
+    fn non_constant_index() {
+        // a[i] with `field[3] a`
+        let e: FieldElementExpression<Bn128Field> = FieldElementExpression::select(
+            ArrayExpressionInner::Identifier("a".into()).annotate(Type::FieldElement, 3u32),
+            UExpressionInner::Identifier("i".into()).annotate(UBitwidth::B32),
+        );
+
+        assert_eq!(BoundsChecker.fold_field_expression(e.clone()), Ok(e));
+    }
+}
@@ -0,0 +1,317 @@
+//! Module containing the inference of bounds of field elements in the reduced program
+//!
+//! Some statements imply bounds on field elements: unpacking `x` to `N` bits implies `x < 2**N`,
+//! converting a `u32` to a field element yields a value below `2**32`, and an assertion such as
+//! `assert(x < c)` restricts `x` for the rest of the program.
+//! These bounds are propagated through additions, multiplications and conditionals, and range checks
+//! which they imply are removed.
+//! Bounds are only tracked below `T::max_unique_value()`, where field comparisons match integer comparisons.
+
+use crate::embed::FlatEmbed;
+use crate::typed_absy::folder::*;
+use crate::typed_absy::*;
+use std::collections::HashMap;
+use zokrates_field::Field;
+
+// the bound implied by a range check on some expression
+enum Bound<T> {
+    // the expression is lower or equal to the value
+    Upper(T),
+    // the expression is greater or equal to the value
+    Lower(T),
+}
+
+#[derive(Default)]
+pub struct BoundsInference<'ast, T> {
+    // the known inclusive range of each identifier
+    ranges: HashMap<Identifier<'ast>, (T, T)>,
+}
+
+impl<'ast, T: Field> BoundsInference<'ast, T> {
+    pub fn infer(p: TypedProgram<'ast, T>) -> TypedProgram<'ast, T> {
+        BoundsInference::default().fold_program(p)
+    }
+
+    fn range(&self, e: &FieldElementExpression<'ast, T>) -> Option<(T, T)> {
+        match e {
+            FieldElementExpression::Number(n) if *n <= T::max_unique_value() => {
+                Some((n.clone(), n.clone()))
+            }
+            FieldElementExpression::Identifier(id) => self.ranges.get(id).cloned(),
+            FieldElementExpression::Add(left, right) => {
+                let (left_min, left_max) = self.range(left)?;
+                let (right_min, right_max) = self.range(right)?;
+                Some((
+                    left_min.checked_add(&right_min)?,
+                    left_max.checked_add(&right_max)?,
+                ))
+            }
+            FieldElementExpression::Mult(left, right) => {
+                let (left_min, left_max) = self.range(left)?;
+                let (right_min, right_max) = self.range(right)?;
+                Some((
+                    left_min.checked_mul(&right_min)?,
+                    left_max.checked_mul(&right_max)?,
+                ))
+            }
+            FieldElementExpression::IfElse(e) => {
+                let (consequence_min, consequence_max) = self.range(&e.consequence)?;
+                let (alternative_min, alternative_max) = self.range(&e.alternative)?;
+                Some((
+                    std::cmp::min(consequence_min, alternative_min),
+                    std::cmp::max(consequence_max, alternative_max),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    // the range of `2**bitwidth - 1` values starting at zero, if it is tracked
+    fn bitwidth_range(bitwidth: usize) -> Option<(T, T)> {
+        if bitwidth >= T::get_required_bits() - 1 {
+            return None;
+        }
+
+        Some((T::zero(), T::from(2).pow(bitwidth) - T::one()))
+    }
+
+    fn insert_range(&mut self, id: Identifier<'ast>, range: (T, T)) {
+        let range = match self.ranges.remove(&id) {
+            Some((min, max)) => (std::cmp::max(min, range.0), std::cmp::min(max, range.1)),
+            None => range,
+        };
+        self.ranges.insert(id, range);
+    }
+
+    fn infer_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        match s {
+            TypedStatement::Assertion(e) => {
+                let check = range_check(&e).map(|(checked, bound)| {
+                    let implied = match (self.range(checked), &bound) {
+                        (Some((_, max)), Bound::Upper(c)) => max <= *c,
+                        (Some((min, _)), Bound::Lower(c)) => min >= *c,
+                        (None, _) => false,
+                    };
+                    (checked.clone(), bound, implied)
+                });
+
+                match check {
+                    Some((_, _, true)) => vec![],
+                    Some((FieldElementExpression::Identifier(id), bound, false)) => {
+                        // a lower bound alone does not imply that `id` is below `T::max_unique_value()`
+                        match bound {
+                            Bound::Upper(c) => self.insert_range(id, (T::zero(), c)),
+                            Bound::Lower(c) => {
+                                if let Some((_, max)) = self.ranges.get(&id).cloned() {
+                                    self.insert_range(id, (c, max));
+                                }
+                            }
+                        }
+                        vec![TypedStatement::Assertion(e)]
+                    }
+                    _ => vec![TypedStatement::Assertion(e)],
+                }
+            }
+            TypedStatement::Definition(TypedAssignee::Identifier(var), e) => {
+                self.ranges.remove(&var.id);
+
+                if let TypedExpression::FieldElement(e) = &e {
+                    if let Some(range) = self.range(e) {
+                        self.ranges.insert(var.id.clone(), range);
+                    }
+                }
+
+                vec![TypedStatement::Definition(
+                    TypedAssignee::Identifier(var),
+                    e,
+                )]
+            }
+            TypedStatement::MultipleDefinition(assignees, rhs) => {
+                for a in &assignees {
+                    if let TypedAssignee::Identifier(var) = a {
+                        self.ranges.remove(&var.id);
+                    }
+                }
+
+                if let TypedExpressionListInner::EmbedCall(embed, generics, arguments) = &rhs.inner
+                {
+                    match (embed, &assignees[..], &arguments[..]) {
+                        (
+                            FlatEmbed::Unpack,
+                            _,
+                            [TypedExpression::FieldElement(FieldElementExpression::Identifier(id))],
+                        ) => {
+                            if let Some(range) = Self::bitwidth_range(generics[0] as usize) {
+                                self.insert_range(id.clone(), range);
+                            }
+                        }
+                        (FlatEmbed::U32ToField, [TypedAssignee::Identifier(var)], _) => {
+                            if let Some(range) = Self::bitwidth_range(32) {
+                                self.ranges.insert(var.id.clone(), range);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                vec![TypedStatement::MultipleDefinition(assignees, rhs)]
+            }
+            s => vec![s],
+        }
+    }
+}
+
+// the expression constrained by a range check against a constant, and the implied bound
+fn range_check<'a, 'ast, T: Field>(
+    e: &'a BooleanExpression<'ast, T>,
+) -> Option<(&'a FieldElementExpression<'ast, T>, Bound<T>)> {
+    // the checked expression, the constant, whether the bound is an upper bound and whether it is strict
+    let (e, c, upper, strict) = match e {
+        BooleanExpression::FieldLt(left, right) => match (&**left, &**right) {
+            (e, FieldElementExpression::Number(c)) => (e, c, true, true),
+            (FieldElementExpression::Number(c), e) => (e, c, false, true),
+            _ => return None,
+        },
+        BooleanExpression::FieldLe(left, right) => match (&**left, &**right) {
+            (e, FieldElementExpression::Number(c)) => (e, c, true, false),
+            (FieldElementExpression::Number(c), e) => (e, c, false, false),
+            _ => return None,
+        },
+        BooleanExpression::FieldGt(left, right) => match (&**left, &**right) {
+            (e, FieldElementExpression::Number(c)) => (e, c, false, true),
+            (FieldElementExpression::Number(c), e) => (e, c, true, true),
+            _ => return None,
+        },
+        BooleanExpression::FieldGe(left, right) => match (&**left, &**right) {
+            (e, FieldElementExpression::Number(c)) => (e, c, false, false),
+            (FieldElementExpression::Number(c), e) => (e, c, true, false),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    if *c > T::max_unique_value() {
+        return None;
+    }
+
+    let bound = match (upper, strict) {
+        (true, true) if *c == T::zero() => return None,
+        (true, true) => Bound::Upper(c.clone() - T::one()),
+        (true, false) => Bound::Upper(c.clone()),
+        (false, true) => Bound::Lower(c.checked_add(&T::one())?),
+        (false, false) => Bound::Lower(c.clone()),
+    };
+
+    Some((e, bound))
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for BoundsInference<'ast, T> {
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        fold_statement(self, s)
+            .into_iter()
+            .flat_map(|s| self.infer_statement(s))
+            .collect()
+    }
+
+    fn fold_block_expression<E: Fold<'ast, T>>(
+        &mut self,
+        block: BlockExpression<'ast, T, E>,
+    ) -> BlockExpression<'ast, T, E> {
+        // bounds learnt in a block, for example in an isolated branch, do not hold after it
+        let ranges = self.ranges.clone();
+        let block = fold_block_expression(self, block);
+        self.ranges = ranges;
+        block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typed_absy::types::Type;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn remove_implied_range_check() {
+        // def main(field x):
+        //      bool[8] bits = unpack::<8>(x)
+        //      assert(x < 256)
+        //      assert(x < 200)
+        //      assert(x + 1 <= 256)
+        //      return
+
+        // expected:
+        // def main(field x):
+        //      bool[8] bits = unpack::<8>(x)
+        //      assert(x < 200)
+        //      return
+
+        let unpack = TypedStatement::MultipleDefinition(
+            vec![Variable::array("bits", Type::Boolean, 8u32).into()],
+            TypedExpressionList {
+                inner: TypedExpressionListInner::EmbedCall(
+                    FlatEmbed::Unpack,
+                    vec![8],
+                    vec![FieldElementExpression::Identifier("x".into()).into()],
+                ),
+                types: Types::new(vec![Type::array((Type::Boolean, 8u32))]),
+            },
+        );
+
+        let implied = TypedStatement::Assertion(BooleanExpression::FieldLt(
+            box FieldElementExpression::Identifier("x".into()),
+            box FieldElementExpression::Number(Bn128Field::from(256)),
+        ));
+
+        let unimplied = TypedStatement::Assertion(BooleanExpression::FieldLt(
+            box FieldElementExpression::Identifier("x".into()),
+            box FieldElementExpression::Number(Bn128Field::from(200)),
+        ));
+
+        let implied_by_sum = TypedStatement::Assertion(BooleanExpression::FieldLe(
+            box FieldElementExpression::Add(
+                box FieldElementExpression::Identifier("x".into()),
+                box FieldElementExpression::Number(Bn128Field::from(1)),
+            ),
+            box FieldElementExpression::Number(Bn128Field::from(256)),
+        ));
+
+        let mut inference = BoundsInference::default();
+
+        let statements: Vec<_> = vec![
+            unpack.clone(),
+            implied,
+            unimplied.clone(),
+            implied_by_sum,
+            TypedStatement::Return(vec![]),
+        ]
+        .into_iter()
+        .flat_map(|s| inference.fold_statement(s))
+        .collect();
+
+        assert_eq!(
+            statements,
+            vec![unpack, unimplied, TypedStatement::Return(vec![])]
+        );
+    }
+
+    #[test]
+    fn keep_unimplied_range_check() {
+        // def main(field x):
+        //      assert(x < 256)
+        //      assert(x < 256)
+        //      return
+
+        // expected: the first check is kept as nothing bounds `x`, the second one is removed
+
+        let check = TypedStatement::Assertion(BooleanExpression::FieldLt(
+            box FieldElementExpression::Identifier("x".into()),
+            box FieldElementExpression::Number(Bn128Field::from(256)),
+        ));
+
+        let mut inference = BoundsInference::default();
+
+        assert_eq!(inference.fold_statement(check.clone()), vec![check.clone()]);
+        assert_eq!(inference.fold_statement(check), vec![]);
+    }
+}
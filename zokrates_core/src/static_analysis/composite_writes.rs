@@ -0,0 +1,64 @@
+//! Module containing helpers for the passes which remember expressions of the reduced program
+//!
+//! Writes to array elements and struct members such as `v[0] = x` do not create a new version of `v`, so an
+//! expression reading `v` may not hold the same value before and after such a write.
+
+use crate::typed_absy::folder::*;
+use crate::typed_absy::*;
+use std::collections::HashSet;
+use zokrates_field::Field;
+
+// the variables whose elements or members are written by a statement, including in the blocks it contains
+pub fn written_variables<'ast, T: Field>(
+    s: &TypedStatement<'ast, T>,
+) -> HashSet<CoreIdentifier<'ast>> {
+    let mut writes = Writes::default();
+    writes.fold_statement(s.clone());
+    writes.variables
+}
+
+#[derive(Default)]
+struct Writes<'ast> {
+    variables: HashSet<CoreIdentifier<'ast>>,
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for Writes<'ast> {
+    fn fold_assignee(&mut self, a: TypedAssignee<'ast, T>) -> TypedAssignee<'ast, T> {
+        if !matches!(a, TypedAssignee::Identifier(..)) {
+            self.variables.insert(root(&a).id.id.clone());
+        }
+        a
+    }
+}
+
+fn root<'a, 'ast, T>(a: &'a TypedAssignee<'ast, T>) -> &'a Variable<'ast, T> {
+    match a {
+        TypedAssignee::Identifier(v) => v,
+        TypedAssignee::Select(a, _) | TypedAssignee::Member(a, _) => root(a),
+    }
+}
+
+// whether `e` reads any version of one of the `variables`
+pub fn reads<'ast, T: Field>(
+    e: TypedExpression<'ast, T>,
+    variables: &HashSet<CoreIdentifier<'ast>>,
+) -> bool {
+    let mut reads = Reads {
+        variables,
+        found: false,
+    };
+    reads.fold_expression(e);
+    reads.found
+}
+
+struct Reads<'a, 'ast> {
+    variables: &'a HashSet<CoreIdentifier<'ast>>,
+    found: bool,
+}
+
+impl<'a, 'ast, T: Field> Folder<'ast, T> for Reads<'a, 'ast> {
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        self.found |= self.variables.contains(&n.id);
+        n
+    }
+}
@@ -0,0 +1,167 @@
+//! Module containing the elimination of copies in the reduced program
+//!
+//! Inlining leaves behind definitions of the form `a = b` for the input and output bindings of each call.
+//! On the SSA program, uses of `a` can be replaced by `b` and the definition removed, so that calls to
+//! thin helpers such as `def id(field a) -> field: return a` cost nothing.
+//! Only basic types are handled: copies of arrays and structs are kept.
+
+use crate::typed_absy::folder::*;
+use crate::typed_absy::*;
+use std::collections::HashMap;
+use zokrates_field::Field;
+
+#[derive(Default)]
+pub struct CopyEliminator<'ast> {
+    // the identifier each eliminated copy refers to
+    copies: HashMap<Identifier<'ast>, Identifier<'ast>>,
+}
+
+impl<'ast> CopyEliminator<'ast> {
+    pub fn eliminate<T: Field>(p: TypedProgram<'ast, T>) -> TypedProgram<'ast, T> {
+        CopyEliminator::default().fold_program(p)
+    }
+}
+
+// the variable copied by `e`, if `e` is an identifier of a basic type
+pub fn copy_source<'ast, T>(e: &TypedExpression<'ast, T>) -> Option<Identifier<'ast>> {
+    match e {
+        TypedExpression::FieldElement(FieldElementExpression::Identifier(id)) => Some(id.clone()),
+        TypedExpression::Boolean(BooleanExpression::Identifier(id)) => Some(id.clone()),
+        TypedExpression::Uint(e) => match e.as_inner() {
+            UExpressionInner::Identifier(id) => Some(id.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for CopyEliminator<'ast> {
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        match s {
+            TypedStatement::Definition(TypedAssignee::Identifier(var), e) => {
+                let e = self.fold_expression(e);
+
+                match copy_source(&e) {
+                    Some(source) => {
+                        self.copies.insert(var.id, source);
+                        vec![]
+                    }
+                    None => vec![TypedStatement::Definition(
+                        TypedAssignee::Identifier(self.fold_variable(var)),
+                        e,
+                    )],
+                }
+            }
+            s => fold_statement(self, s),
+        }
+    }
+
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        // the map is kept flat: the source of a copy is never a copy itself
+        self.copies.get(&n).cloned().unwrap_or(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::static_analysis::reducer::{reduce_program, ReducerConfig};
+    use crate::typed_absy::types::{DeclarationSignature, GGenericsAssignment};
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn identity_call() {
+        // def id(field a) -> field:
+        //      return a
+        // def main(field x) -> field:
+        //      field y = id(x)
+        //      return y
+
+        // expected after reduction and copy elimination:
+        // def main(field x) -> field:
+        //      # PUSH CALL to id
+        //      # POP CALL
+        //      return x
+
+        let signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let id_key =
+            DeclarationFunctionKey::with_location("main", "id").signature(signature.clone());
+        let main_key =
+            DeclarationFunctionKey::with_location("main", "main").signature(signature.clone());
+
+        let id: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![
+                FieldElementExpression::Identifier("a".into()).into(),
+            ])],
+            signature: signature.clone(),
+        };
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("x").into()],
+            statements: vec![
+                TypedStatement::Definition(
+                    Variable::field_element("y").into(),
+                    FieldElementExpression::function_call(
+                        id_key.clone(),
+                        vec![],
+                        vec![FieldElementExpression::Identifier("x".into()).into()],
+                    )
+                    .into(),
+                ),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("y".into()).into()]),
+            ],
+            signature: signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (id_key.clone(), TypedFunctionSymbol::Here(id)),
+                        (main_key.clone(), TypedFunctionSymbol::Here(main)),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced = reduce_program(p, &ReducerConfig::default()).unwrap();
+
+        let expected_main = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("x").into()],
+            statements: vec![
+                TypedStatement::PushCallLog(id_key, GGenericsAssignment::default()),
+                TypedStatement::PopCallLog,
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("x".into()).into()]),
+            ],
+            signature: signature.clone(),
+        };
+
+        let expected: TypedProgram<Bn128Field> = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(main_key, TypedFunctionSymbol::Here(expected_main))]
+                        .into_iter()
+                        .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        assert_eq!(CopyEliminator::eliminate(reduced), expected);
+    }
+}
@@ -0,0 +1,362 @@
+//! Module containing the elimination of dead definitions in the reduced program
+//!
+//! Inlining leaves behind many definitions whose variable is never read. On the SSA program, such a definition
+//! can be removed as long as computing its value does not constrain the program. The analysis is conservative:
+//! assertions, writes to array elements or struct members and multiple definitions are always kept, as well as
+//! definitions involving function calls, blocks, divisions, field comparisons or selections at a non-constant index.
+//! When removing the definitions reading a parameter would leave it unread, the last of them is kept, as it may be the
+//! only constraint on this parameter.
+
+use crate::typed_absy::folder::*;
+use crate::typed_absy::*;
+use std::collections::HashSet;
+use zokrates_field::Field;
+
+pub struct DeadCodeEliminator;
+
+impl DeadCodeEliminator {
+    pub fn eliminate<T: Field>(p: TypedProgram<T>) -> TypedProgram<T> {
+        DeadCodeEliminator.fold_program(p)
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for DeadCodeEliminator {
+    fn fold_function(&mut self, f: TypedFunction<'ast, T>) -> TypedFunction<'ast, T> {
+        let (statements, used) = eliminate(f.statements.clone(), &HashSet::new());
+
+        // removing the definitions reading a parameter may leave it unconstrained, so the last of them is kept
+        let pinned: HashSet<_> = f
+            .arguments
+            .iter()
+            .map(|p| p.id.id.clone())
+            .filter(|id| !used.contains(id))
+            .collect();
+
+        let statements = match pinned.is_empty() {
+            true => statements,
+            false => eliminate(f.statements, &pinned).0,
+        };
+
+        TypedFunction { statements, ..f }
+    }
+}
+
+// remove the definitions of variables which are never read, keeping the last definition reading each of the `pinned`
+// variables, and return the remaining statements along with the variables they read
+fn eliminate<'ast, T: Field>(
+    statements: Vec<TypedStatement<'ast, T>>,
+    pinned: &HashSet<Identifier<'ast>>,
+) -> (Vec<TypedStatement<'ast, T>>, HashSet<Identifier<'ast>>) {
+    // the variables read by the statements visited so far, going backwards
+    let mut used = HashSet::new();
+
+    let mut statements: Vec<_> = statements
+        .into_iter()
+        .rev()
+        .map(|s| {
+            let mut usage = Usage::default();
+
+            match s {
+                TypedStatement::Definition(TypedAssignee::Identifier(v), e) => {
+                    let e = usage.fold_expression(e);
+
+                    let last_read = usage
+                        .used
+                        .iter()
+                        .any(|id| pinned.contains(id) && !used.contains(id));
+
+                    match usage.effects || last_read || used.contains(&v.id) {
+                        true => {
+                            used.extend(usage.used);
+                            vec![TypedStatement::Definition(TypedAssignee::Identifier(v), e)]
+                        }
+                        false => vec![],
+                    }
+                }
+                s => {
+                    let s = usage.fold_statement(s);
+                    used.extend(usage.used);
+                    s
+                }
+            }
+        })
+        .collect();
+
+    statements.reverse();
+
+    (statements.into_iter().flatten().collect(), used)
+}
+
+// whether computing `e` may constrain the program
+pub fn has_effects<T: Field>(e: TypedExpression<T>) -> bool {
+    let mut usage = Usage::default();
+    usage.fold_expression(e);
+    usage.effects
+}
+
+// the variables read by an expression or a statement, and whether it may constrain the program
+#[derive(Default)]
+struct Usage<'ast> {
+    used: HashSet<Identifier<'ast>>,
+    effects: bool,
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for Usage<'ast> {
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        self.used.insert(n.clone());
+        n
+    }
+
+    fn fold_block_expression<E: Fold<'ast, T>>(
+        &mut self,
+        block: BlockExpression<'ast, T, E>,
+    ) -> BlockExpression<'ast, T, E> {
+        self.effects |= !block.statements.is_empty();
+        fold_block_expression(self, block)
+    }
+
+    fn fold_function_call_expression<
+        E: Id<'ast, T> + From<TypedExpression<'ast, T>> + Expr<'ast, T> + FunctionCall<'ast, T>,
+    >(
+        &mut self,
+        ty: &E::Ty,
+        e: FunctionCallExpression<'ast, T, E>,
+    ) -> FunctionCallOrExpression<'ast, T, E> {
+        self.effects = true;
+        fold_function_call_expression(self, ty, e)
+    }
+
+    fn fold_select_expression<
+        E: Expr<'ast, T> + Select<'ast, T> + IfElse<'ast, T> + From<TypedExpression<'ast, T>>,
+    >(
+        &mut self,
+        ty: &E::Ty,
+        e: SelectExpression<'ast, T, E>,
+    ) -> SelectOrExpression<'ast, T, E> {
+        // a selection at a non-constant index is checked to be in bounds
+        self.effects |= !matches!(e.index.as_inner(), UExpressionInner::Value(..));
+        fold_select_expression(self, ty, e)
+    }
+
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        self.effects |= matches!(e, FieldElementExpression::Div(..));
+        fold_field_expression(self, e)
+    }
+
+    fn fold_uint_expression_inner(
+        &mut self,
+        bitwidth: UBitwidth,
+        e: UExpressionInner<'ast, T>,
+    ) -> UExpressionInner<'ast, T> {
+        self.effects |= matches!(e, UExpressionInner::Div(..) | UExpressionInner::Rem(..));
+        fold_uint_expression_inner(self, bitwidth, e)
+    }
+
+    fn fold_boolean_expression(
+        &mut self,
+        e: BooleanExpression<'ast, T>,
+    ) -> BooleanExpression<'ast, T> {
+        // field comparisons check that their operands are in range
+        self.effects |= matches!(
+            e,
+            BooleanExpression::FieldLt(..)
+                | BooleanExpression::FieldLe(..)
+                | BooleanExpression::FieldGe(..)
+                | BooleanExpression::FieldGt(..)
+        );
+        fold_boolean_expression(self, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    fn function(
+        statements: Vec<TypedStatement<'static, Bn128Field>>,
+    ) -> TypedFunction<'static, Bn128Field> {
+        TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements,
+            signature: DeclarationSignature::new()
+                .inputs(vec![DeclarationType::FieldElement])
+                .outputs(vec![DeclarationType::FieldElement]),
+        }
+    }
+
+    #[test]
+    fn remove_unused_definitions() {
+        // def main(field a) -> field:
+        //      field b = a * a
+        //      field c = b + a
+        //      field d = a + a
+        //      return b
+
+        // expected:
+        // def main(field a) -> field:
+        //      field b = a * a
+        //      return b
+
+        let b = TypedStatement::Definition(
+            Variable::field_element("b").into(),
+            FieldElementExpression::Mult(
+                box FieldElementExpression::Identifier("a".into()),
+                box FieldElementExpression::Identifier("a".into()),
+            )
+            .into(),
+        );
+        let ret =
+            TypedStatement::Return(vec![FieldElementExpression::Identifier("b".into()).into()]);
+
+        let f = function(vec![
+            b.clone(),
+            TypedStatement::Definition(
+                Variable::field_element("c").into(),
+                FieldElementExpression::Add(
+                    box FieldElementExpression::Identifier("b".into()),
+                    box FieldElementExpression::Identifier("a".into()),
+                )
+                .into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("d").into(),
+                FieldElementExpression::Add(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Identifier("a".into()),
+                )
+                .into(),
+            ),
+            ret.clone(),
+        ]);
+
+        assert_eq!(DeadCodeEliminator.fold_function(f), function(vec![b, ret]));
+    }
+
+    #[test]
+    fn keep_transitively_used_definitions() {
+        // def main(field a) -> field:
+        //      field b = a * a
+        //      field c = b + a
+        //      return c
+
+        let f = function(vec![
+            TypedStatement::Definition(
+                Variable::field_element("b").into(),
+                FieldElementExpression::Mult(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Identifier("a".into()),
+                )
+                .into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("c").into(),
+                FieldElementExpression::Add(
+                    box FieldElementExpression::Identifier("b".into()),
+                    box FieldElementExpression::Identifier("a".into()),
+                )
+                .into(),
+            ),
+            TypedStatement::Return(vec![FieldElementExpression::Identifier("c".into()).into()]),
+        ]);
+
+        assert_eq!(DeadCodeEliminator.fold_function(f.clone()), f);
+    }
+
+    #[test]
+    fn keep_assertions() {
+        // def main(field a) -> field:
+        //      field b = a * a
+        //      field c = a / a
+        //      assert(b == a)
+        //      return a
+
+        let f = function(vec![
+            TypedStatement::Definition(
+                Variable::field_element("b").into(),
+                FieldElementExpression::Mult(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Identifier("a".into()),
+                )
+                .into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("c").into(),
+                FieldElementExpression::Div(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Identifier("a".into()),
+                )
+                .into(),
+            ),
+            TypedStatement::Assertion(BooleanExpression::FieldEq(
+                box FieldElementExpression::Identifier("b".into()),
+                box FieldElementExpression::Identifier("a".into()),
+            )),
+            TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
+        ]);
+
+        // `b` is read by the assertion, and the unused `c` is kept as the division constrains `a` to be nonzero
+        assert_eq!(DeadCodeEliminator.fold_function(f.clone()), f);
+    }
+
+    #[test]
+    fn keep_element_writes() {
+        // def main(field a) -> field:
+        //      field[2] b = [a, a]
+        //      b[0] = a
+        //      return a
+
+        let f = function(vec![
+            TypedStatement::Definition(
+                Variable::array("b", Type::FieldElement, 2u32).into(),
+                ArrayExpressionInner::Value(
+                    vec![
+                        FieldElementExpression::Identifier("a".into()).into(),
+                        FieldElementExpression::Identifier("a".into()).into(),
+                    ]
+                    .into(),
+                )
+                .annotate(Type::FieldElement, 2u32)
+                .into(),
+            ),
+            TypedStatement::Definition(
+                TypedAssignee::Select(
+                    box Variable::array("b", Type::FieldElement, 2u32).into(),
+                    box 0u32.into(),
+                ),
+                FieldElementExpression::Identifier("a".into()).into(),
+            ),
+            TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
+        ]);
+
+        assert_eq!(DeadCodeEliminator.fold_function(f.clone()), f);
+    }
+
+    #[test]
+    fn keep_last_read_of_parameter() {
+        // def main(field a) -> field:
+        //      field b = a * a
+        //      return 1
+
+        // `b` is never read, but removing it would leave `a` unconstrained
+
+        let f = function(vec![
+            TypedStatement::Definition(
+                Variable::field_element("b").into(),
+                FieldElementExpression::Mult(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Identifier("a".into()),
+                )
+                .into(),
+            ),
+            TypedStatement::Return(vec![
+                FieldElementExpression::Number(Bn128Field::from(1)).into()
+            ]),
+        ]);
+
+        assert_eq!(DeadCodeEliminator.fold_function(f.clone()), f);
+    }
+}
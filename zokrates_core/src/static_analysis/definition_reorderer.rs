@@ -0,0 +1,261 @@
+// Reorder independent definitions so that the definitions feeding a given statement are grouped right before it
+//
+// Example:
+// ```
+// a = x + 1
+// b = x * 2
+// c = a + 1
+// d = b + 1
+// ```
+//
+// Becomes
+// ```
+// a = x + 1
+// c = a + 1
+// b = x * 2
+// d = b + 1
+// ```
+//
+// Only sequences of consecutive definitions are reordered: any other statement acts as a barrier.
+// Inside a sequence, all dependencies between definitions (read after write, write after read and
+// write after write) are preserved.
+
+use crate::zir::folder::*;
+use crate::zir::*;
+use std::collections::{HashMap, HashSet};
+use zokrates_field::Field;
+
+pub struct DefinitionReorderer;
+
+impl DefinitionReorderer {
+    pub fn reorder<T: Field>(p: ZirProgram<T>) -> ZirProgram<T> {
+        ZirProgram {
+            main: ZirFunction {
+                statements: reorder_statements(p.main.statements),
+                ..p.main
+            },
+        }
+    }
+}
+
+fn reorder_statements<T: Field>(statements: Vec<ZirStatement<T>>) -> Vec<ZirStatement<T>> {
+    let mut res = vec![];
+    let mut definitions = vec![];
+
+    for s in statements {
+        match s {
+            ZirStatement::Definition(a, e) => definitions.push((a, e)),
+            s => {
+                res.extend(reorder_definitions(std::mem::take(&mut definitions)));
+                res.push(match s {
+                    ZirStatement::IfElse(condition, consequence, alternative) => {
+                        ZirStatement::IfElse(
+                            condition,
+                            reorder_statements(consequence),
+                            reorder_statements(alternative),
+                        )
+                    }
+                    s => s,
+                });
+            }
+        }
+    }
+
+    res.extend(reorder_definitions(definitions));
+    res
+}
+
+fn reorder_definitions<'ast, T: Field>(
+    definitions: Vec<(ZirAssignee<'ast>, ZirExpression<'ast, T>)>,
+) -> Vec<ZirStatement<'ast, T>> {
+    // for each definition, the indices of the definitions which must come before it
+    let mut dependencies: Vec<Vec<usize>> = vec![vec![]; definitions.len()];
+    let mut has_dependents = vec![false; definitions.len()];
+
+    // the index of the latest definition of each identifier
+    let mut definers: HashMap<&Identifier<'ast>, usize> = HashMap::new();
+    // the indices of the definitions reading each identifier since its latest definition
+    let mut readers: HashMap<Identifier<'ast>, Vec<usize>> = HashMap::new();
+
+    for (index, (assignee, expression)) in definitions.iter().enumerate() {
+        let reads = ReadCollector::collect(expression.clone());
+
+        let mut deps: Vec<usize> = reads
+            .iter()
+            .filter_map(|id| definers.get(id).cloned())
+            .chain(definers.get(&assignee.id).cloned())
+            .chain(readers.remove(&assignee.id).unwrap_or_default())
+            .collect();
+
+        deps.sort_unstable();
+        deps.dedup();
+
+        for d in &deps {
+            has_dependents[*d] = true;
+        }
+
+        dependencies[index] = deps;
+
+        for id in reads {
+            readers.entry(id).or_default().push(index);
+        }
+
+        definers.insert(&assignee.id, index);
+    }
+
+    // starting from each definition which no other definition depends on, emit the dependencies
+    // of each definition right before it
+    let mut order = Vec::with_capacity(definitions.len());
+    let mut emitted = vec![false; definitions.len()];
+
+    for sink in (0..definitions.len()).filter(|i| !has_dependents[*i]) {
+        // use an explicit stack as dependency chains can be very long
+        let mut stack = vec![(sink, 0)];
+
+        while let Some((index, next)) = stack.pop() {
+            match dependencies[index].get(next) {
+                Some(&dependency) => {
+                    stack.push((index, next + 1));
+                    if !emitted[dependency] {
+                        stack.push((dependency, 0));
+                    }
+                }
+                None => {
+                    emitted[index] = true;
+                    order.push(index);
+                }
+            }
+        }
+    }
+
+    let mut definitions: Vec<_> = definitions.into_iter().map(Some).collect();
+
+    order
+        .into_iter()
+        .map(|index| {
+            let (assignee, expression) = definitions[index].take().unwrap();
+            ZirStatement::Definition(assignee, expression)
+        })
+        .collect()
+}
+
+// Collect the identifiers read by an expression
+#[derive(Default)]
+struct ReadCollector<'ast> {
+    reads: HashSet<Identifier<'ast>>,
+}
+
+impl<'ast> ReadCollector<'ast> {
+    fn collect<T: Field>(e: ZirExpression<'ast, T>) -> HashSet<Identifier<'ast>> {
+        let mut collector = ReadCollector::default();
+        collector.fold_expression(e);
+        collector.reads
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for ReadCollector<'ast> {
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        self.reads.insert(n.clone());
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zir::types::Signature;
+    use zokrates_field::Bn128Field;
+
+    fn definition<'ast>(
+        id: &'ast str,
+        e: FieldElementExpression<'ast, Bn128Field>,
+    ) -> ZirStatement<'ast, Bn128Field> {
+        ZirStatement::Definition(Variable::field_element(id), e.into())
+    }
+
+    fn add(id: &str, n: usize) -> FieldElementExpression<Bn128Field> {
+        FieldElementExpression::Add(
+            box FieldElementExpression::Identifier(id.into()),
+            box FieldElementExpression::Number(Bn128Field::from(n)),
+        )
+    }
+
+    fn program(statements: Vec<ZirStatement<Bn128Field>>) -> ZirProgram<Bn128Field> {
+        ZirProgram {
+            main: ZirFunction {
+                arguments: vec![],
+                statements,
+                signature: Signature::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn group_by_consumer() {
+        // a = x + 1
+        // b = x + 2
+        // c = a + 1
+        // d = b + 1
+        // return c, d
+
+        // expected:
+        // a = x + 1
+        // c = a + 1
+        // b = x + 2
+        // d = b + 1
+        // return c, d
+
+        let ret = ZirStatement::Return(vec![
+            FieldElementExpression::Identifier("c".into()).into(),
+            FieldElementExpression::Identifier("d".into()).into(),
+        ]);
+
+        let p = program(vec![
+            definition("a", add("x", 1)),
+            definition("b", add("x", 2)),
+            definition("c", add("a", 1)),
+            definition("d", add("b", 1)),
+            ret.clone(),
+        ]);
+
+        let expected = program(vec![
+            definition("a", add("x", 1)),
+            definition("c", add("a", 1)),
+            definition("b", add("x", 2)),
+            definition("d", add("b", 1)),
+            ret,
+        ]);
+
+        assert_eq!(DefinitionReorderer::reorder(p), expected);
+    }
+
+    #[test]
+    fn keep_redefinitions_in_order() {
+        // a = x + 1
+        // b = a + 1
+        // a = y + 1
+        // c = a + 1
+        // assert(b == c)
+        // d = x + 2
+
+        // expected: unchanged, as each definition depends on the previous one and
+        // the assertion is a barrier
+
+        let statements = vec![
+            definition("a", add("x", 1)),
+            definition("b", add("a", 1)),
+            definition("a", add("y", 1)),
+            definition("c", add("a", 1)),
+            ZirStatement::Assertion(BooleanExpression::FieldEq(
+                box FieldElementExpression::Identifier("b".into()),
+                box FieldElementExpression::Identifier("c".into()),
+            )),
+            definition("d", add("x", 2)),
+        ];
+
+        assert_eq!(
+            DefinitionReorderer::reorder(program(statements.clone())),
+            program(statements)
+        );
+    }
+}
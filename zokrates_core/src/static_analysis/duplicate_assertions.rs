@@ -0,0 +1,162 @@
+//! Module containing the removal of duplicate assertions in the reduced program
+//!
+//! On the SSA program, an assertion which is syntactically identical to a previous one checks the same values,
+//! so it always holds and is removed. Such duplicates typically appear after loop unrolling, when several
+//! iterations check the same variable.
+//! A write to an array element or a struct member does not create a new version of the variable, so the assertions
+//! reading this variable are checked again after such a write.
+
+use crate::static_analysis::composite_writes::{reads, written_variables};
+use crate::typed_absy::folder::*;
+use crate::typed_absy::*;
+use std::collections::HashSet;
+use zokrates_field::Field;
+
+#[derive(Default)]
+pub struct DuplicateAssertionRemover<'ast, T> {
+    // the assertions made so far
+    assertions: HashSet<BooleanExpression<'ast, T>>,
+}
+
+impl<'ast, T: Field> DuplicateAssertionRemover<'ast, T> {
+    pub fn remove(p: TypedProgram<'ast, T>) -> TypedProgram<'ast, T> {
+        DuplicateAssertionRemover::default().fold_program(p)
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for DuplicateAssertionRemover<'ast, T> {
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        let written = written_variables(&s);
+
+        let res = match s {
+            TypedStatement::Assertion(e) => {
+                let e = self.fold_boolean_expression(e);
+
+                match self.assertions.insert(e.clone()) {
+                    true => vec![TypedStatement::Assertion(e)],
+                    false => vec![],
+                }
+            }
+            s => fold_statement(self, s),
+        };
+
+        // the values read from the written variables change, so the assertions on them must be checked again
+        if !written.is_empty() {
+            self.assertions
+                .retain(|e| !reads(e.clone().into(), &written));
+        }
+
+        res
+    }
+
+    fn fold_block_expression<E: Fold<'ast, T>>(
+        &mut self,
+        block: BlockExpression<'ast, T, E>,
+    ) -> BlockExpression<'ast, T, E> {
+        // assertions made in a block, for example in an isolated branch, do not hold after it
+        let assertions = self.assertions.clone();
+        let block = fold_block_expression(self, block);
+        self.assertions = assertions;
+        block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn remove_duplicates() {
+        // assert(x < 256)
+        // assert(x < 256)
+        // assert(y < 256)
+        // assert(x < 256)
+        // return
+
+        // expected:
+        // assert(x < 256)
+        // assert(y < 256)
+        // return
+
+        let check = |name: &'static str| {
+            TypedStatement::Assertion(BooleanExpression::FieldLt(
+                box FieldElementExpression::Identifier(name.into()),
+                box FieldElementExpression::Number(Bn128Field::from(256)),
+            ))
+        };
+
+        let mut remover = DuplicateAssertionRemover::default();
+
+        let statements: Vec<_> = vec![
+            check("x"),
+            check("x"),
+            check("y"),
+            check("x"),
+            TypedStatement::Return(vec![]),
+        ]
+        .into_iter()
+        .flat_map(|s| remover.fold_statement(s))
+        .collect();
+
+        assert_eq!(
+            statements,
+            vec![check("x"), check("y"), TypedStatement::Return(vec![])]
+        );
+    }
+
+    #[test]
+    fn element_write() {
+        // field[2] v = [x, x]
+        // assert(v[0] == 1)
+        // v[0] = y
+        // assert(v[0] == 1)
+        // return
+
+        // `v[0]` reads a different value after the write, so both assertions are kept
+
+        let check = TypedStatement::Assertion(BooleanExpression::FieldEq(
+            box FieldElementExpression::select(
+                ArrayExpressionInner::Identifier("v".into()).annotate(Type::FieldElement, 2u32),
+                0u32,
+            ),
+            box FieldElementExpression::Number(Bn128Field::from(1)),
+        ));
+
+        let statements = vec![
+            TypedStatement::Definition(
+                Variable::array("v", Type::FieldElement, 2u32).into(),
+                ArrayExpressionInner::Value(
+                    vec![
+                        FieldElementExpression::Identifier("x".into()).into(),
+                        FieldElementExpression::Identifier("x".into()).into(),
+                    ]
+                    .into(),
+                )
+                .annotate(Type::FieldElement, 2u32)
+                .into(),
+            ),
+            check.clone(),
+            TypedStatement::Definition(
+                TypedAssignee::Select(
+                    box Variable::array("v", Type::FieldElement, 2u32).into(),
+                    box 0u32.into(),
+                ),
+                FieldElementExpression::Identifier("y".into()).into(),
+            ),
+            check,
+            TypedStatement::Return(vec![]),
+        ];
+
+        let mut remover = DuplicateAssertionRemover::default();
+
+        assert_eq!(
+            statements
+                .clone()
+                .into_iter()
+                .flat_map(|s| remover.fold_statement(s))
+                .collect::<Vec<_>>(),
+            statements
+        );
+    }
+}
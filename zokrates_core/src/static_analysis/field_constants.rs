@@ -0,0 +1,113 @@
+//! Module containing the extraction of field constants from a program
+//!
+//! This is meant to be run on a reduced program, for example to build constant tables
+//! for backends which support lookups.
+
+use crate::typed_absy::folder::*;
+use crate::typed_absy::{FieldElementExpression, TypedFunctionSymbol, TypedProgram};
+use std::collections::HashMap;
+use zokrates_field::Field;
+
+pub struct FieldConstantCollector<T> {
+    constants: HashMap<T, usize>,
+}
+
+impl<T> FieldConstantCollector<T> {
+    fn new() -> Self {
+        FieldConstantCollector {
+            constants: HashMap::new(),
+        }
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for FieldConstantCollector<T> {
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        match e {
+            FieldElementExpression::Number(n) => {
+                *self.constants.entry(n.clone()).or_default() += 1;
+                FieldElementExpression::Number(n)
+            }
+            e => fold_field_expression(self, e),
+        }
+    }
+}
+
+impl<'ast, T: Field> TypedProgram<'ast, T> {
+    /// Returns the number of occurrences of each field constant in the functions of the program.
+    /// The distinct constants are the keys of the returned map.
+    pub fn field_constants(&self) -> HashMap<T, usize> {
+        let mut collector = FieldConstantCollector::new();
+
+        for symbol in self.modules.values().flat_map(|m| m.functions.values()) {
+            if let TypedFunctionSymbol::Here(f) = symbol {
+                collector.fold_function(f.clone());
+            }
+        }
+
+        collector.constants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typed_absy::types::DeclarationSignature;
+    use crate::typed_absy::{
+        DeclarationFunctionKey, DeclarationType, DeclarationVariable, TypedFunction, TypedModule,
+        TypedStatement,
+    };
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn collect() {
+        // def main(field a) -> field:
+        //      return 1 + a * 2 + 1
+
+        let signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![FieldElementExpression::Add(
+                box FieldElementExpression::Add(
+                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                    box FieldElementExpression::Mult(
+                        box FieldElementExpression::Identifier("a".into()),
+                        box FieldElementExpression::Number(Bn128Field::from(2)),
+                    ),
+                ),
+                box FieldElementExpression::Number(Bn128Field::from(1)),
+            )
+            .into()])],
+            signature: signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main").signature(signature),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let expected: HashMap<_, _> = vec![(Bn128Field::from(1), 2), (Bn128Field::from(2), 1)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(p.field_constants(), expected);
+    }
+}
@@ -683,6 +683,11 @@ fn fold_field_expression<'ast, T: Field>(
         typed_absy::FieldElementExpression::Pos(box e) => {
             f.fold_field_expression(statements_buffer, e)
         }
+        typed_absy::FieldElementExpression::BoolToField(box e) => {
+            let e = f.fold_boolean_expression(statements_buffer, e);
+
+            zir::FieldElementExpression::BoolToField(box e)
+        }
         typed_absy::FieldElementExpression::IfElse(c) => f
             .fold_if_else_expression(statements_buffer, c)
             .pop()
@@ -889,6 +894,24 @@ fn fold_uint_expression<'ast, T: Field>(
         .annotate(e.bitwidth.to_usize())
 }
 
+// lower a rotation to the combination of shifts `(e << by) | (e >> (bitwidth - by))`
+fn rotate_left<T: Field>(
+    e: zir::UExpression<T>,
+    by: u32,
+    bitwidth: UBitwidth,
+) -> zir::UExpressionInner<T> {
+    let bitwidth = bitwidth.to_usize();
+    let by = by % bitwidth as u32;
+
+    match by {
+        0 => e.into_inner(),
+        by => zir::UExpressionInner::Or(
+            box zir::UExpressionInner::LeftShift(box e.clone(), by).annotate(bitwidth),
+            box zir::UExpressionInner::RightShift(box e, bitwidth as u32 - by).annotate(bitwidth),
+        ),
+    }
+}
+
 fn fold_uint_expression_inner<'ast, T: Field>(
     f: &mut Flattener<T>,
     statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
@@ -983,6 +1006,17 @@ fn fold_uint_expression_inner<'ast, T: Field>(
 
             zir::UExpressionInner::RightShift(box e, *by as u32)
         }
+        typed_absy::UExpressionInner::RotateLeft(box e, by) => {
+            let e = f.fold_uint_expression(statements_buffer, e);
+
+            rotate_left(e, by, bitwidth)
+        }
+        typed_absy::UExpressionInner::RotateRight(box e, by) => {
+            let e = f.fold_uint_expression(statements_buffer, e);
+            let by = by % bitwidth.to_usize() as u32;
+
+            rotate_left(e, bitwidth.to_usize() as u32 - by, bitwidth)
+        }
         typed_absy::UExpressionInner::Not(box e) => {
             let e = f.fold_uint_expression(statements_buffer, e);
 
@@ -0,0 +1,227 @@
+//! Module containing the rewriting of polynomial evaluations to Horner form
+//!
+//! A field element expression which is a polynomial in a single variable `x` with constant coefficients, such as
+//! `c0 + c1 * x + c2 * x**2 + c3 * x**3`, is rewritten to `((c3 * x + c2) * x + c1) * x + c0`, which requires
+//! one multiplication per degree minus one.
+//! The rewriting is only applied when it requires fewer multiplications than the original expression.
+
+use crate::typed_absy::folder::*;
+use crate::typed_absy::*;
+use zokrates_field::Field;
+
+pub struct HornerRewriter;
+
+impl HornerRewriter {
+    pub fn rewrite<T: Field>(p: TypedProgram<T>) -> TypedProgram<T> {
+        HornerRewriter.fold_program(p)
+    }
+}
+
+// the number of multiplications of two non-constant values required to flatten an expression
+fn multiplication_count<T: Field>(e: &FieldElementExpression<T>) -> Option<usize> {
+    match e {
+        FieldElementExpression::Number(_) | FieldElementExpression::Identifier(_) => Some(0),
+        FieldElementExpression::Add(left, right) | FieldElementExpression::Sub(left, right) => {
+            Some(multiplication_count(left)? + multiplication_count(right)?)
+        }
+        FieldElementExpression::Neg(e) => multiplication_count(e),
+        FieldElementExpression::Mult(left, right) => {
+            let nonlinear = !matches!(**left, FieldElementExpression::Number(_))
+                && !matches!(**right, FieldElementExpression::Number(_));
+            Some(multiplication_count(left)? + multiplication_count(right)? + nonlinear as usize)
+        }
+        // exponentiation is flattened by square and multiply
+        FieldElementExpression::Pow(base, exponent) => match exponent.as_inner() {
+            UExpressionInner::Value(0) => Some(0),
+            UExpressionInner::Value(n) => Some(
+                multiplication_count(base)?
+                    + (128 - n.leading_zeros() as usize - 1)
+                    + (n.count_ones() as usize - 1),
+            ),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// the coefficients of an expression seen as a polynomial of degree at most `max_degree` in a single variable
+fn to_polynomial<'ast, T: Field>(
+    e: &FieldElementExpression<'ast, T>,
+    variable: &mut Option<Identifier<'ast>>,
+    max_degree: usize,
+) -> Option<Vec<T>> {
+    let polynomial = match e {
+        FieldElementExpression::Number(n) => vec![n.clone()],
+        FieldElementExpression::Identifier(id) => {
+            if variable.as_ref().map_or(false, |v| v != id) {
+                return None;
+            }
+            *variable = Some(id.clone());
+            vec![T::zero(), T::one()]
+        }
+        FieldElementExpression::Add(left, right) => add(
+            to_polynomial(left, variable, max_degree)?,
+            to_polynomial(right, variable, max_degree)?,
+        ),
+        FieldElementExpression::Sub(left, right) => add(
+            to_polynomial(left, variable, max_degree)?,
+            neg(to_polynomial(right, variable, max_degree)?),
+        ),
+        FieldElementExpression::Neg(e) => neg(to_polynomial(e, variable, max_degree)?),
+        FieldElementExpression::Mult(left, right) => mul(
+            to_polynomial(left, variable, max_degree)?,
+            to_polynomial(right, variable, max_degree)?,
+        ),
+        FieldElementExpression::Pow(base, exponent) => match exponent.as_inner() {
+            UExpressionInner::Value(n) if *n as usize <= max_degree => {
+                let base = to_polynomial(base, variable, max_degree)?;
+                (0..*n).fold(vec![T::one()], |acc, _| mul(acc, base.clone()))
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    match polynomial.len() > max_degree + 1 {
+        true => None,
+        false => Some(polynomial),
+    }
+}
+
+fn add<T: Field>(left: Vec<T>, right: Vec<T>) -> Vec<T> {
+    let (mut long, short) = match left.len() >= right.len() {
+        true => (left, right),
+        false => (right, left),
+    };
+    for (i, c) in short.into_iter().enumerate() {
+        long[i] = long[i].clone() + c;
+    }
+    trim(long)
+}
+
+fn neg<T: Field>(p: Vec<T>) -> Vec<T> {
+    p.into_iter().map(|c| T::zero() - c).collect()
+}
+
+fn mul<T: Field>(left: Vec<T>, right: Vec<T>) -> Vec<T> {
+    let mut res = vec![T::zero(); left.len() + right.len() - 1];
+    for (i, l) in left.iter().enumerate() {
+        for (j, r) in right.iter().enumerate() {
+            res[i + j] = res[i + j].clone() + l.clone() * r.clone();
+        }
+    }
+    trim(res)
+}
+
+// remove the zero coefficients of the highest degrees, keeping at least one coefficient
+fn trim<T: Field>(mut p: Vec<T>) -> Vec<T> {
+    while p.len() > 1 && p.last() == Some(&T::zero()) {
+        p.pop();
+    }
+    p
+}
+
+// build `((c_n * x + c_{n-1}) * x + ...) * x + c_0`
+fn horner<T: Field>(coefficients: Vec<T>, variable: Identifier) -> FieldElementExpression<T> {
+    let mut coefficients = coefficients.into_iter().rev();
+    let leading = FieldElementExpression::Number(coefficients.next().unwrap());
+
+    coefficients.fold(leading, |acc, c| {
+        let acc = FieldElementExpression::Mult(
+            box acc,
+            box FieldElementExpression::Identifier(variable.clone()),
+        );
+        match c == T::zero() {
+            true => acc,
+            false => FieldElementExpression::Add(box acc, box FieldElementExpression::Number(c)),
+        }
+    })
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for HornerRewriter {
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        if let Some(count) = multiplication_count(&e) {
+            // Horner form requires `degree - 1` multiplications, so only polynomials of degree up to `count` are cheaper
+            let mut variable = None;
+            if let Some(coefficients) = to_polynomial(&e, &mut variable, count) {
+                if let Some(variable) = variable {
+                    if coefficients.len() > 2 && coefficients.len() - 2 < count {
+                        return horner(coefficients, variable);
+                    }
+                }
+            }
+        }
+
+        fold_field_expression(self, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::{Bn128Field, Pow};
+
+    fn x() -> FieldElementExpression<'static, Bn128Field> {
+        FieldElementExpression::Identifier("x".into())
+    }
+
+    fn number(n: usize) -> FieldElementExpression<'static, Bn128Field> {
+        FieldElementExpression::Number(Bn128Field::from(n))
+    }
+
+    fn evaluate(e: &FieldElementExpression<Bn128Field>, x: &Bn128Field) -> Bn128Field {
+        match e {
+            FieldElementExpression::Number(n) => n.clone(),
+            FieldElementExpression::Identifier(_) => x.clone(),
+            FieldElementExpression::Add(left, right) => evaluate(left, x) + evaluate(right, x),
+            FieldElementExpression::Mult(left, right) => evaluate(left, x) * evaluate(right, x),
+            FieldElementExpression::Pow(base, exponent) => match exponent.as_inner() {
+                UExpressionInner::Value(n) => evaluate(base, x).pow(*n as usize),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn degree_four() {
+        // 3 + 2 * x + 5 * x**2 + 7 * x**3 + 4 * x**4
+        let e = (1..5).fold(number(3), |acc, i| {
+            FieldElementExpression::Add(
+                box acc,
+                box FieldElementExpression::Mult(
+                    box number([0, 2, 5, 7, 4][i]),
+                    box FieldElementExpression::Pow(box x(), box (i as u32).into()),
+                ),
+            )
+        });
+
+        // (((4 * x + 7) * x + 5) * x + 2) * x + 3
+        let expected = [7, 5, 2, 3].iter().fold(number(4), |acc, c| {
+            FieldElementExpression::Add(
+                box FieldElementExpression::Mult(box acc, box x()),
+                box number(*c),
+            )
+        });
+
+        let rewritten = HornerRewriter.fold_field_expression(e.clone());
+
+        assert_eq!(rewritten, expected);
+
+        for sample in &[0, 1, 2, 42, 1000] {
+            let sample = Bn128Field::from(*sample);
+            assert_eq!(evaluate(&rewritten, &sample), evaluate(&e, &sample));
+        }
+    }
+
+    #[test]
+    fn keep_cheaper_power() {
+        // x**8 requires three squarings, fewer than the seven multiplications of Horner form
+        let e = FieldElementExpression::Pow(box x(), box 8u32.into());
+
+        assert_eq!(HornerRewriter.fold_field_expression(e.clone()), e);
+    }
+}
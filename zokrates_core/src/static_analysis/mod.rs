@@ -5,26 +5,48 @@
 //! @date 2018
 
 mod bounds_checker;
+mod bounds_inference;
 mod branch_isolator;
+mod composite_writes;
 mod constant_inliner;
+mod copy_eliminator;
+mod dead_code;
+mod definition_reorderer;
+mod duplicate_assertions;
+mod field_constants;
 mod flat_propagation;
 mod flatten_complex_types;
+mod horner;
 mod propagation;
 mod reducer;
 mod shift_checker;
+mod subexpressions;
 mod uint_optimizer;
 mod unconstrained_vars;
+mod value_numbering;
 mod variable_read_remover;
 mod variable_write_remover;
 
 use self::bounds_checker::BoundsChecker;
+use self::bounds_inference::BoundsInference;
 use self::branch_isolator::Isolator;
+use self::copy_eliminator::CopyEliminator;
+use self::dead_code::DeadCodeEliminator;
+use self::definition_reorderer::DefinitionReorderer;
+use self::duplicate_assertions::DuplicateAssertionRemover;
 use self::flatten_complex_types::Flattener;
+use self::horner::HornerRewriter;
 use self::propagation::Propagator;
-use self::reducer::reduce_program;
+use self::reducer::reduce_program_with_report;
+pub use self::reducer::{
+    reduce_function, reduce_program_partial, InlinePolicy, ReduceOutcome, ReducerConfig,
+    ReductionStats, Warning,
+};
 use self::shift_checker::ShiftChecker;
+use self::subexpressions::SubexpressionEliminator;
 use self::uint_optimizer::UintOptimizer;
 use self::unconstrained_vars::UnconstrainedVariableDetector;
+use self::value_numbering::ValueNumbering;
 use self::variable_read_remover::VariableReadRemover;
 use self::variable_write_remover::VariableWriteRemover;
 use crate::compile::CompileConfig;
@@ -44,6 +66,7 @@ pub enum Error {
     Reducer(self::reducer::Error),
     OutOfBounds(self::bounds_checker::Error),
     Propagation(self::propagation::Error),
+    NonConstantShift(self::shift_checker::Error),
 }
 
 impl From<self::reducer::Error> for Error {
@@ -64,18 +87,28 @@ impl From<self::propagation::Error> for Error {
     }
 }
 
+impl From<self::shift_checker::Error> for Error {
+    fn from(e: shift_checker::Error) -> Self {
+        Error::NonConstantShift(e)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::Reducer(e) => write!(f, "{}", e),
             Error::OutOfBounds(e) => write!(f, "{}", e),
             Error::Propagation(e) => write!(f, "{}", e),
+            Error::NonConstantShift(e) => write!(f, "{}", e),
         }
     }
 }
 
 impl<'ast, T: Field> TypedProgram<'ast, T> {
-    pub fn analyse(self, config: &CompileConfig) -> Result<(ZirProgram<'ast, T>, Abi), Error> {
+    pub fn analyse(
+        self,
+        config: &CompileConfig,
+    ) -> Result<(ZirProgram<'ast, T>, Abi, ReductionStats, Vec<Warning>), Error> {
         // inline user-defined constants
         let r = ConstantInliner::inline(self);
         // isolate branches
@@ -86,12 +119,34 @@ impl<'ast, T: Field> TypedProgram<'ast, T> {
         };
 
         // reduce the program to a single function
-        let r = reduce_program(r).map_err(Error::from)?;
+        let (r, stats, warnings) = reduce_program_with_report(
+            r,
+            &ReducerConfig {
+                max_instantiations: config.max_instantiations,
+                merge_public_inputs: config.merge_public_inputs,
+                ..ReducerConfig::default()
+            },
+        )
+        .map_err(Error::from)?;
         // generate abi
         let abi = r.abi();
 
         // propagate
         let r = Propagator::propagate(r).map_err(Error::from)?;
+        // remove copies left by inlining
+        let r = CopyEliminator::eliminate(r);
+        // compute repeated subexpressions once
+        let r = SubexpressionEliminator::eliminate(r);
+        // rewrite provably equal variables to a single representative
+        let r = ValueNumbering::apply(r);
+        // remove assertions identical to a previous one
+        let r = DuplicateAssertionRemover::remove(r);
+        // remove definitions which are never read
+        let r = DeadCodeEliminator::eliminate(r);
+        // rewrite polynomial evaluations to Horner form
+        let r = HornerRewriter::rewrite(r);
+        // remove range checks implied by known bounds
+        let r = BoundsInference::infer(r);
         // remove assignment to variable index
         let r = VariableWriteRemover::apply(r);
         // remove variable access to complex types
@@ -104,8 +159,14 @@ impl<'ast, T: Field> TypedProgram<'ast, T> {
         let zir = Flattener::flatten(r);
         // optimize uint expressions
         let zir = UintOptimizer::optimize(zir);
+        // group definitions by consumer
+        let zir = if config.reorder_definitions {
+            DefinitionReorderer::reorder(zir)
+        } else {
+            zir
+        };
 
-        Ok((zir, abi))
+        Ok((zir, abi, stats, warnings))
     }
 }
 
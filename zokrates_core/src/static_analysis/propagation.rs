@@ -24,6 +24,7 @@ pub enum Error {
     AssertionFailed(String),
     OutOfBounds(u128, u128),
     NonConstantExponent(String),
+    DivisionByZero(String),
 }
 
 impl fmt::Display for Error {
@@ -41,6 +42,41 @@ impl fmt::Display for Error {
                 "Non-constant exponent `{}` detected during static analysis",
                 s
             ),
+            Error::DivisionByZero(s) => write!(
+                f,
+                "Division by zero `{}` detected during static analysis",
+                s
+            ),
+        }
+    }
+}
+
+// Inside a branch of a conditional, resolve the nested conditionals on the same condition to the branch taken
+struct BranchCondition<'ast, 'b, T> {
+    condition: &'b BooleanExpression<'ast, T>,
+    value: bool,
+}
+
+impl<'ast, 'b, T: Field> ResultFolder<'ast, T> for BranchCondition<'ast, 'b, T> {
+    type Error = Error;
+
+    fn fold_if_else_expression<
+        E: Expr<'ast, T> + IfElse<'ast, T> + PartialEq + ResultFold<'ast, T>,
+    >(
+        &mut self,
+        ty: &E::Ty,
+        e: IfElseExpression<'ast, T, E>,
+    ) -> Result<IfElseOrExpression<'ast, T, E>, Self::Error> {
+        if *e.condition == *self.condition {
+            let branch = match self.value {
+                true => *e.consequence,
+                false => *e.alternative,
+            };
+            Ok(IfElseOrExpression::Expression(
+                branch.fold(self)?.into_inner(),
+            ))
+        } else {
+            fold_if_else_expression(self, ty, e)
         }
     }
 }
@@ -124,7 +160,7 @@ impl<'ast, 'a, T: Field> Propagator<'ast, 'a, T> {
     }
 }
 
-fn is_constant<T: Field>(e: &TypedExpression<T>) -> bool {
+pub fn is_constant<T: Field>(e: &TypedExpression<T>) -> bool {
     match e {
         TypedExpression::FieldElement(FieldElementExpression::Number(..)) => true,
         TypedExpression::Boolean(BooleanExpression::Value(..)) => true,
@@ -244,6 +280,51 @@ fn to_canonical_constant<T: Field>(e: TypedExpression<T>) -> TypedExpression<T>
     }
 }
 
+// the equality of two elements of the same type
+fn element_eq<'ast, T: Field>(
+    e1: TypedExpression<'ast, T>,
+    e2: TypedExpression<'ast, T>,
+) -> BooleanExpression<'ast, T> {
+    match (e1, e2) {
+        (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+            BooleanExpression::FieldEq(box e1, box e2)
+        }
+        (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
+            BooleanExpression::BoolEq(box e1, box e2)
+        }
+        (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
+            BooleanExpression::UintEq(box e1, box e2)
+        }
+        (TypedExpression::Array(e1), TypedExpression::Array(e2)) => {
+            BooleanExpression::ArrayEq(box e1, box e2)
+        }
+        (TypedExpression::Struct(e1), TypedExpression::Struct(e2)) => {
+            BooleanExpression::StructEq(box e1, box e2)
+        }
+        _ => unreachable!("compared elements should have the same type"),
+    }
+}
+
+// the conjunction of non-empty `terms` as a balanced tree, so that its depth stays logarithmic in the number of terms
+fn conjunction<T>(mut terms: Vec<BooleanExpression<T>>) -> BooleanExpression<T> {
+    match terms.len() {
+        1 => terms.pop().unwrap(),
+        len => {
+            let right = terms.split_off(len / 2);
+            BooleanExpression::And(box conjunction(terms), box conjunction(right))
+        }
+    }
+}
+
+// rotate `v` to the left by `by` places on `bitwidth` bits, where `by` is lower than `bitwidth`
+fn rotate_left(v: u128, by: u32, bitwidth: UBitwidth) -> u128 {
+    let bitwidth = bitwidth.to_usize() as u32;
+    match by {
+        0 => v,
+        by => ((v << by) | (v >> (bitwidth - by))) & (2_u128.pow(bitwidth) - 1),
+    }
+}
+
 impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
     type Error = Error;
 
@@ -297,26 +378,42 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
         _: &E::Ty,
         e: IfElseExpression<'ast, T, E>,
     ) -> Result<IfElseOrExpression<'ast, T, E>, Self::Error> {
-        Ok(
-            match (
-                self.fold_boolean_expression(*e.condition)?,
-                e.consequence.fold(self)?,
-                e.alternative.fold(self)?,
-            ) {
-                (BooleanExpression::Value(true), consequence, _) => {
-                    IfElseOrExpression::Expression(consequence.into_inner())
-                }
-                (BooleanExpression::Value(false), _, alternative) => {
-                    IfElseOrExpression::Expression(alternative.into_inner())
-                }
-                (_, consequence, alternative) if consequence == alternative => {
-                    IfElseOrExpression::Expression(consequence.into_inner())
-                }
-                (condition, consequence, alternative) => IfElseOrExpression::IfElse(
-                    IfElseExpression::new(condition, consequence, alternative),
-                ),
-            },
-        )
+        let condition = self.fold_boolean_expression(*e.condition)?;
+
+        // only the live branch is folded when the condition is known, as the dead one may not be valid, for
+        // example when it selects outside of an array in the base case of a generic function
+        match condition {
+            BooleanExpression::Value(true) => {
+                return Ok(IfElseOrExpression::Expression(
+                    e.consequence.fold(self)?.into_inner(),
+                ))
+            }
+            BooleanExpression::Value(false) => {
+                return Ok(IfElseOrExpression::Expression(
+                    e.alternative.fold(self)?.into_inner(),
+                ))
+            }
+            _ => {}
+        };
+
+        // `if c then (if c then a else b) else d` is `if c then a else d`, and symmetrically in the alternative
+        let consequence = e.consequence.fold(self)?.fold(&mut BranchCondition {
+            condition: &condition,
+            value: true,
+        })?;
+        let alternative = e.alternative.fold(self)?.fold(&mut BranchCondition {
+            condition: &condition,
+            value: false,
+        })?;
+
+        Ok(match (condition, consequence, alternative) {
+            (_, consequence, alternative) if consequence == alternative => {
+                IfElseOrExpression::Expression(consequence.into_inner())
+            }
+            (condition, consequence, alternative) => IfElseOrExpression::IfElse(
+                IfElseExpression::new(condition, consequence, alternative),
+            ),
+        })
     }
 
     fn fold_statement(
@@ -843,6 +940,14 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 self.fold_uint_expression(e1)?.into_inner(),
                 self.fold_uint_expression(e2)?.into_inner(),
             ) {
+                (e1, UExpressionInner::Value(0)) => Err(Error::DivisionByZero(
+                    UExpressionInner::Div(
+                        box e1.annotate(bitwidth),
+                        box UExpressionInner::Value(0).annotate(bitwidth),
+                    )
+                    .annotate(bitwidth)
+                    .to_string(),
+                )),
                 (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
                     Ok(UExpressionInner::Value(
                         (v1 / v2) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
@@ -864,6 +969,14 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 self.fold_uint_expression(e1)?.into_inner(),
                 self.fold_uint_expression(e2)?.into_inner(),
             ) {
+                (e1, UExpressionInner::Value(0)) => Err(Error::DivisionByZero(
+                    UExpressionInner::Rem(
+                        box e1.annotate(bitwidth),
+                        box UExpressionInner::Value(0).annotate(bitwidth),
+                    )
+                    .annotate(bitwidth)
+                    .to_string(),
+                )),
                 (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
                     Ok(UExpressionInner::Value(
                         (v1 % v2) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
@@ -907,6 +1020,77 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     )),
                 }
             }
+            UExpressionInner::RotateLeft(box e, by) => {
+                let by = by % bitwidth.to_usize() as u32;
+                match self.fold_uint_expression(e)?.into_inner() {
+                    UExpressionInner::Value(v) => {
+                        Ok(UExpressionInner::Value(rotate_left(v, by, bitwidth)))
+                    }
+                    // rotating by a multiple of the bitwidth is the identity
+                    e if by == 0 => Ok(e),
+                    e => Ok(UExpressionInner::RotateLeft(box e.annotate(bitwidth), by)),
+                }
+            }
+            // canonicalize right rotations to left rotations
+            UExpressionInner::RotateRight(box e, by) => {
+                let by = by % bitwidth.to_usize() as u32;
+                self.fold_uint_expression_inner(
+                    bitwidth,
+                    UExpressionInner::RotateLeft(box e, bitwidth.to_usize() as u32 - by),
+                )
+            }
+            UExpressionInner::Or(box e1, box e2) => match (
+                self.fold_uint_expression(e1)?.into_inner(),
+                self.fold_uint_expression(e2)?.into_inner(),
+            ) {
+                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
+                    Ok(UExpressionInner::Value(v1 | v2))
+                }
+                (UExpressionInner::Value(0), e) | (e, UExpressionInner::Value(0)) => Ok(e),
+                // recognize rotations composed from shifts
+                (
+                    UExpressionInner::LeftShift(
+                        box e1,
+                        box UExpression {
+                            inner: UExpressionInner::Value(left),
+                            ..
+                        },
+                    ),
+                    UExpressionInner::RightShift(
+                        box e2,
+                        box UExpression {
+                            inner: UExpressionInner::Value(right),
+                            ..
+                        },
+                    ),
+                )
+                | (
+                    UExpressionInner::RightShift(
+                        box e2,
+                        box UExpression {
+                            inner: UExpressionInner::Value(right),
+                            ..
+                        },
+                    ),
+                    UExpressionInner::LeftShift(
+                        box e1,
+                        box UExpression {
+                            inner: UExpressionInner::Value(left),
+                            ..
+                        },
+                    ),
+                ) if e1 == e2
+                    && left > 0
+                    && right > 0
+                    && left + right == bitwidth.to_usize() as u128 =>
+                {
+                    Ok(UExpressionInner::RotateLeft(box e1, left as u32))
+                }
+                (e1, e2) => Ok(UExpressionInner::Or(
+                    box e1.annotate(bitwidth),
+                    box e2.annotate(bitwidth),
+                )),
+            },
             UExpressionInner::Xor(box e1, box e2) => match (
                 self.fold_uint_expression(e1)?.into_inner(),
                 self.fold_uint_expression(e2)?.into_inner(),
@@ -995,15 +1179,10 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 }
                 (e1, e2) => Ok(FieldElementExpression::Add(box e1, box e2)),
             },
-            FieldElementExpression::Sub(box e1, box e2) => match (
-                self.fold_field_expression(e1)?,
-                self.fold_field_expression(e2)?,
-            ) {
-                (FieldElementExpression::Number(n1), FieldElementExpression::Number(n2)) => {
-                    Ok(FieldElementExpression::Number(n1 - n2))
-                }
-                (e1, e2) => Ok(FieldElementExpression::Sub(box e1, box e2)),
-            },
+            // canonicalize `a - b` to `a + (-b)` so that simplifications only have to handle additions
+            FieldElementExpression::Sub(box e1, box e2) => self.fold_field_expression(
+                FieldElementExpression::Add(box e1, box FieldElementExpression::Neg(box e2)),
+            ),
             FieldElementExpression::Mult(box e1, box e2) => match (
                 self.fold_field_expression(e1)?,
                 self.fold_field_expression(e2)?,
@@ -1017,9 +1196,19 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 self.fold_field_expression(e1)?,
                 self.fold_field_expression(e2)?,
             ) {
+                (e1, FieldElementExpression::Number(n2)) if n2 == T::zero() => {
+                    Err(Error::DivisionByZero(
+                        FieldElementExpression::Div(box e1, box n2.into()).to_string(),
+                    ))
+                }
                 (FieldElementExpression::Number(n1), FieldElementExpression::Number(n2)) => {
                     Ok(FieldElementExpression::Number(n1 / n2))
                 }
+                // dividing by a constant is multiplying by its inverse
+                (e1, FieldElementExpression::Number(n2)) => Ok(FieldElementExpression::Mult(
+                    box e1,
+                    box FieldElementExpression::Number(n2.inverse_mul().unwrap()),
+                )),
                 (e1, e2) => Ok(FieldElementExpression::Div(box e1, box e2)),
             },
             FieldElementExpression::Neg(box e) => match self.fold_field_expression(e)? {
@@ -1032,6 +1221,36 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 FieldElementExpression::Number(n) => Ok(FieldElementExpression::Number(n)),
                 e => Ok(FieldElementExpression::Pos(box e)),
             },
+            // `if b then 1 else 0` is the conversion of `b` to a field element, which is free once flattened
+            FieldElementExpression::IfElse(e) => {
+                match self.fold_if_else_expression(&Type::FieldElement, e)? {
+                    IfElseOrExpression::IfElse(e) => match (&*e.consequence, &*e.alternative) {
+                        (
+                            FieldElementExpression::Number(n1),
+                            FieldElementExpression::Number(n2),
+                        ) if *n1 == T::one() && *n2 == T::zero() => {
+                            Ok(FieldElementExpression::BoolToField(e.condition))
+                        }
+                        (
+                            FieldElementExpression::Number(n1),
+                            FieldElementExpression::Number(n2),
+                        ) if *n1 == T::zero() && *n2 == T::one() => {
+                            Ok(FieldElementExpression::BoolToField(
+                                box BooleanExpression::Not(e.condition),
+                            ))
+                        }
+                        _ => Ok(FieldElementExpression::IfElse(e)),
+                    },
+                    IfElseOrExpression::Expression(e) => Ok(e),
+                }
+            }
+            FieldElementExpression::BoolToField(box e) => match self.fold_boolean_expression(e)? {
+                BooleanExpression::Value(v) => Ok(FieldElementExpression::Number(match v {
+                    true => T::one(),
+                    false => T::zero(),
+                })),
+                e => Ok(FieldElementExpression::BoolToField(box e)),
+            },
             FieldElementExpression::Pow(box e1, box e2) => {
                 let e1 = self.fold_field_expression(e1)?;
                 let e2 = self.fold_uint_expression(e2)?;
@@ -1146,6 +1365,22 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                         )),
                     }
                 }
+                // push the index inside the conditional: `(if c then a else b)[n]` is `if c then a[n] else b[n]`
+                (ArrayExpressionInner::IfElse(c), UExpressionInner::Value(n)) => {
+                    if n < size {
+                        let e = TypedExpression::if_else(
+                            *c.condition,
+                            TypedExpression::select(*c.consequence, n as u32),
+                            TypedExpression::select(*c.alternative, n as u32),
+                        );
+
+                        Ok(SelectOrExpression::Expression(
+                            E::from(self.fold_expression(e)?).into_inner(),
+                        ))
+                    } else {
+                        Err(Error::OutOfBounds(n, size))
+                    }
+                }
                 (a, i) => Ok(SelectOrExpression::Select(SelectExpression::new(
                     a.annotate(inner_type, size as u32),
                     i.annotate(UBitwidth::B32),
@@ -1170,6 +1405,52 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 },
                 None => Ok(ArrayExpressionInner::Identifier(id)),
             },
+            ArrayExpressionInner::Slice(box array, box from, box to) => {
+                let array = self.fold_array_expression(array)?;
+                let from = self.fold_uint_expression(from)?;
+                let to = self.fold_uint_expression(to)?;
+
+                // if the slice selects exactly one of the spreads of a concatenation, we return that spread
+                // for example `[...a, ...b][0..2]` with `a` of size 2 becomes `a`
+                let spread = match (array.as_inner(), from.as_inner(), to.as_inner()) {
+                    (
+                        ArrayExpressionInner::Value(v),
+                        UExpressionInner::Value(from),
+                        UExpressionInner::Value(to),
+                    ) => {
+                        let mut offset = 0;
+                        let mut spread = None;
+
+                        for e in v.0.iter() {
+                            let size = match e.size().into_inner() {
+                                UExpressionInner::Value(size) => size,
+                                _ => break,
+                            };
+
+                            if offset > *from {
+                                break;
+                            }
+
+                            if let TypedExpressionOrSpread::Spread(s) = e {
+                                if offset == *from && offset + size == *to {
+                                    spread = Some(s.array.as_inner().clone());
+                                    break;
+                                }
+                            }
+
+                            offset += size;
+                        }
+
+                        spread
+                    }
+                    _ => None,
+                };
+
+                match spread {
+                    Some(e) => Ok(e),
+                    None => Ok(ArrayExpressionInner::Slice(box array, box from, box to)),
+                }
+            }
             e => fold_array_expression_inner(self, ty, e),
         }
     }
@@ -1211,8 +1492,8 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
         &mut self,
         e: BooleanExpression<'ast, T>,
     ) -> Result<BooleanExpression<'ast, T>, Error> {
-        // Note: we only propagate when we see constants, as comparing of arbitrary expressions would lead to
-        // a lot of false negatives due to expressions not being in a canonical form
+        // Note: we only propagate when we see constants or identical identifiers, as comparing of arbitrary
+        // expressions would lead to a lot of false negatives due to expressions not being in a canonical form
         // For example, `2 * a` is equivalent to `a + a`, but our notion of equality would not detect that here
         // These kind of reduction rules are easier to apply later in the process, when we have canonical representations
         // of expressions, ie `a + a` would always be written `2 * a`
@@ -1232,6 +1513,10 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     (FieldElementExpression::Number(n1), FieldElementExpression::Number(n2)) => {
                         Ok(BooleanExpression::Value(n1 == n2))
                     }
+                    (
+                        FieldElementExpression::Identifier(id1),
+                        FieldElementExpression::Identifier(id2),
+                    ) if id1 == id2 => Ok(BooleanExpression::Value(true)),
                     (e1, e2) => Ok(BooleanExpression::FieldEq(box e1, box e2)),
                 }
             }
@@ -1243,6 +1528,11 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
                         Ok(BooleanExpression::Value(n1 == n2))
                     }
+                    (UExpressionInner::Identifier(id1), UExpressionInner::Identifier(id2))
+                        if id1 == id2 =>
+                    {
+                        Ok(BooleanExpression::Value(true))
+                    }
                     _ => Ok(BooleanExpression::UintEq(box e1, box e2)),
                 }
             }
@@ -1254,6 +1544,11 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     (BooleanExpression::Value(n1), BooleanExpression::Value(n2)) => {
                         Ok(BooleanExpression::Value(n1 == n2))
                     }
+                    (BooleanExpression::Identifier(id1), BooleanExpression::Identifier(id2))
+                        if id1 == id2 =>
+                    {
+                        Ok(BooleanExpression::Value(true))
+                    }
                     (e1, e2) => Ok(BooleanExpression::BoolEq(box e1, box e2)),
                 }
             }
@@ -1273,7 +1568,62 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     }
                 };
 
-                Ok(BooleanExpression::ArrayEq(box e1, box e2))
+                if let (
+                    ArrayExpressionInner::Identifier(id1),
+                    ArrayExpressionInner::Identifier(id2),
+                ) = (e1.as_inner(), e2.as_inner())
+                {
+                    if id1 == id2 {
+                        return Ok(BooleanExpression::Value(true));
+                    }
+                }
+
+                let size = match e1.size().into_inner() {
+                    UExpressionInner::Value(size) => size as u32,
+                    _ => return Ok(BooleanExpression::ArrayEq(box e1, box e2)),
+                };
+
+                let e1: TypedExpression<'ast, T> = e1.into();
+                let e2: TypedExpression<'ast, T> = e2.into();
+
+                // when an operand is constant, compare element-wise so that equal constant elements are folded away
+                // and a single pair of different constant elements makes the whole comparison false
+                match (is_constant(&e1), is_constant(&e2)) {
+                    (false, false) => Ok(BooleanExpression::ArrayEq(
+                        box ArrayExpression::from(e1),
+                        box ArrayExpression::from(e2),
+                    )),
+                    (c1, c2) => {
+                        let e1 = ArrayExpression::from(match c1 {
+                            true => to_canonical_constant(e1),
+                            false => e1,
+                        });
+                        let e2 = ArrayExpression::from(match c2 {
+                            true => to_canonical_constant(e2),
+                            false => e2,
+                        });
+
+                        let mut terms = vec![];
+
+                        for i in 0..size {
+                            match self.fold_boolean_expression(element_eq(
+                                TypedExpression::select(e1.clone(), i),
+                                TypedExpression::select(e2.clone(), i),
+                            ))? {
+                                BooleanExpression::Value(false) => {
+                                    return Ok(BooleanExpression::Value(false))
+                                }
+                                BooleanExpression::Value(true) => {}
+                                eq => terms.push(eq),
+                            }
+                        }
+
+                        match terms.is_empty() {
+                            true => Ok(BooleanExpression::Value(true)),
+                            false => Ok(conjunction(terms)),
+                        }
+                    }
+                }
             }
             BooleanExpression::FieldLt(box e1, box e2) => {
                 let e1 = self.fold_field_expression(e1)?;
@@ -1327,6 +1677,11 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
                         Ok(BooleanExpression::Value(n1 < n2))
                     }
+                    (UExpressionInner::Identifier(id1), UExpressionInner::Identifier(id2))
+                        if id1 == id2 =>
+                    {
+                        Ok(BooleanExpression::Value(false))
+                    }
                     _ => Ok(BooleanExpression::UintLt(box e1, box e2)),
                 }
             }
@@ -1338,6 +1693,11 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
                         Ok(BooleanExpression::Value(n1 <= n2))
                     }
+                    (UExpressionInner::Identifier(id1), UExpressionInner::Identifier(id2))
+                        if id1 == id2 =>
+                    {
+                        Ok(BooleanExpression::Value(true))
+                    }
                     _ => Ok(BooleanExpression::UintLe(box e1, box e2)),
                 }
             }
@@ -1349,6 +1709,11 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
                         Ok(BooleanExpression::Value(n1 > n2))
                     }
+                    (UExpressionInner::Identifier(id1), UExpressionInner::Identifier(id2))
+                        if id1 == id2 =>
+                    {
+                        Ok(BooleanExpression::Value(false))
+                    }
                     _ => Ok(BooleanExpression::UintGt(box e1, box e2)),
                 }
             }
@@ -1360,6 +1725,11 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
                         Ok(BooleanExpression::Value(n1 >= n2))
                     }
+                    (UExpressionInner::Identifier(id1), UExpressionInner::Identifier(id2))
+                        if id1 == id2 =>
+                    {
+                        Ok(BooleanExpression::Value(true))
+                    }
                     _ => Ok(BooleanExpression::UintGe(box e1, box e2)),
                 }
             }
@@ -1407,6 +1777,8 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 let e = self.fold_boolean_expression(e)?;
                 match e {
                     BooleanExpression::Value(v) => Ok(BooleanExpression::Value(!v)),
+                    // !(!x) == x
+                    BooleanExpression::Not(box e) => Ok(e),
                     e => Ok(BooleanExpression::Not(box e)),
                 }
             }
@@ -1454,6 +1826,52 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn sub_canonicalization() {
+                // a - b - c
+                let e = FieldElementExpression::Sub(
+                    box FieldElementExpression::Sub(
+                        box FieldElementExpression::Identifier("a".into()),
+                        box FieldElementExpression::Identifier("b".into()),
+                    ),
+                    box FieldElementExpression::Identifier("c".into()),
+                );
+
+                // a + (-b) + (-c)
+                let expected = FieldElementExpression::Add(
+                    box FieldElementExpression::Add(
+                        box FieldElementExpression::Identifier("a".into()),
+                        box FieldElementExpression::Neg(box FieldElementExpression::Identifier(
+                            "b".into(),
+                        )),
+                    ),
+                    box FieldElementExpression::Neg(box FieldElementExpression::Identifier(
+                        "c".into(),
+                    )),
+                );
+
+                let folded = Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                    .fold_field_expression(e)
+                    .unwrap();
+
+                assert_eq!(folded, expected);
+                assert_eq!(folded.to_string(), "((a - b) - c)");
+
+                // 7 - 2 - 3
+                let e = FieldElementExpression::Sub(
+                    box FieldElementExpression::Sub(
+                        box FieldElementExpression::Number(Bn128Field::from(7)),
+                        box FieldElementExpression::Number(Bn128Field::from(2)),
+                    ),
+                    box FieldElementExpression::Number(Bn128Field::from(3)),
+                );
+
+                assert_eq!(
+                    Propagator::with_constants(&mut Constants::new()).fold_field_expression(e),
+                    Ok(FieldElementExpression::Number(Bn128Field::from(2)))
+                );
+            }
+
             #[test]
             fn mult() {
                 let e = FieldElementExpression::Mult(
@@ -1480,6 +1898,55 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn div_by_constant() {
+                // x / 2 == x * inverse(2)
+                let e = FieldElementExpression::Div(
+                    box FieldElementExpression::Identifier("x".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(2)),
+                );
+
+                assert_eq!(
+                    Propagator::with_constants(&mut Constants::new()).fold_field_expression(e),
+                    Ok(FieldElementExpression::Mult(
+                        box FieldElementExpression::Identifier("x".into()),
+                        box FieldElementExpression::Number(
+                            Bn128Field::from(2).inverse_mul().unwrap()
+                        ),
+                    ))
+                );
+
+                // with `x == 6`, x / 2 == 3
+                let mut constants = Constants::new();
+                constants.insert(
+                    "x".into(),
+                    FieldElementExpression::Number(Bn128Field::from(6)).into(),
+                );
+
+                let e = FieldElementExpression::Div(
+                    box FieldElementExpression::Identifier("x".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(2)),
+                );
+
+                assert_eq!(
+                    Propagator::with_constants(&mut constants).fold_field_expression(e),
+                    Ok(FieldElementExpression::Number(Bn128Field::from(3)))
+                );
+            }
+
+            #[test]
+            fn div_by_zero() {
+                let e = FieldElementExpression::Div(
+                    box FieldElementExpression::Identifier("x".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(0)),
+                );
+
+                assert_eq!(
+                    Propagator::with_constants(&mut Constants::new()).fold_field_expression(e),
+                    Err(Error::DivisionByZero("(x / 0f)".to_string()))
+                );
+            }
+
             #[test]
             fn pow() {
                 let e = FieldElementExpression::Pow(
@@ -1521,6 +1988,116 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn nested_if_else_constant_after_propagation() {
+                // with `c == true`, if c && !(!c) then (if c || d then 2 else 3) else 4 == 2
+                let mut constants = Constants::new();
+                constants.insert("c".into(), BooleanExpression::Value(true).into());
+
+                let c = || BooleanExpression::Identifier("c".into());
+
+                let e = FieldElementExpression::if_else(
+                    BooleanExpression::And(
+                        box c(),
+                        box BooleanExpression::Not(box BooleanExpression::Not(box c())),
+                    ),
+                    FieldElementExpression::if_else(
+                        BooleanExpression::Or(
+                            box c(),
+                            box BooleanExpression::Identifier("d".into()),
+                        ),
+                        FieldElementExpression::Number(Bn128Field::from(2)),
+                        FieldElementExpression::Number(Bn128Field::from(3)),
+                    ),
+                    FieldElementExpression::Number(Bn128Field::from(4)),
+                );
+
+                assert_eq!(
+                    Propagator::with_constants(&mut constants).fold_field_expression(e),
+                    Ok(FieldElementExpression::Number(Bn128Field::from(2)))
+                );
+            }
+
+            #[test]
+            fn nested_if_else_same_condition() {
+                let c = || BooleanExpression::Identifier("c".into());
+                let id = |name: &'static str| {
+                    FieldElementExpression::<Bn128Field>::Identifier(name.into())
+                };
+
+                // if c then (if c then a else b) else d
+                let nested_in_consequence = FieldElementExpression::if_else(
+                    c(),
+                    FieldElementExpression::if_else(c(), id("a"), id("b")),
+                    id("d"),
+                );
+
+                // if c then a else (if c then b else d)
+                let nested_in_alternative = FieldElementExpression::if_else(
+                    c(),
+                    id("a"),
+                    FieldElementExpression::if_else(c(), id("b"), id("d")),
+                );
+
+                // if c then a else d
+                let expected = FieldElementExpression::if_else(c(), id("a"), id("d"));
+
+                assert_eq!(
+                    Propagator::with_constants(&mut Constants::new())
+                        .fold_field_expression(nested_in_consequence),
+                    Ok(expected.clone())
+                );
+                assert_eq!(
+                    Propagator::with_constants(&mut Constants::new())
+                        .fold_field_expression(nested_in_alternative),
+                    Ok(expected)
+                );
+            }
+
+            #[test]
+            fn if_else_to_bool_to_field() {
+                // if b then 1 else 0
+                let e = FieldElementExpression::if_else(
+                    BooleanExpression::Identifier("b".into()),
+                    FieldElementExpression::Number(Bn128Field::from(1)),
+                    FieldElementExpression::Number(Bn128Field::from(0)),
+                );
+
+                assert_eq!(
+                    Propagator::with_constants(&mut Constants::new()).fold_field_expression(e),
+                    Ok(FieldElementExpression::BoolToField(
+                        box BooleanExpression::Identifier("b".into())
+                    ))
+                );
+            }
+
+            #[test]
+            fn inverted_if_else_to_bool_to_field() {
+                // if b then 0 else 1
+                let e = FieldElementExpression::if_else(
+                    BooleanExpression::Identifier("b".into()),
+                    FieldElementExpression::Number(Bn128Field::from(0)),
+                    FieldElementExpression::Number(Bn128Field::from(1)),
+                );
+
+                assert_eq!(
+                    Propagator::with_constants(&mut Constants::new()).fold_field_expression(e),
+                    Ok(FieldElementExpression::BoolToField(
+                        box BooleanExpression::Not(box BooleanExpression::Identifier("b".into()))
+                    ))
+                );
+            }
+
+            #[test]
+            fn bool_to_field_constant() {
+                let e = FieldElementExpression::BoolToField(box BooleanExpression::Value(true));
+
+                assert_eq!(
+                    Propagator::with_constants(&mut Constants::new()).fold_field_expression(e),
+                    Ok(FieldElementExpression::Number(Bn128Field::from(1)))
+                );
+            }
+
             #[test]
             fn select() {
                 let e = FieldElementExpression::select(
@@ -1576,6 +2153,18 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn double_not() {
+                let e: BooleanExpression<Bn128Field> = BooleanExpression::Not(
+                    box BooleanExpression::Not(box BooleanExpression::Identifier("a".into())),
+                );
+
+                assert_eq!(
+                    Propagator::with_constants(&mut Constants::new()).fold_boolean_expression(e),
+                    Ok(BooleanExpression::Identifier("a".into()))
+                );
+            }
+
             #[test]
             fn field_eq() {
                 let e_true = BooleanExpression::FieldEq(
@@ -1735,6 +2324,69 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn identical_operands() {
+                let a = || box FieldElementExpression::<Bn128Field>::Identifier("a".into());
+                let b = || box BooleanExpression::<Bn128Field>::Identifier("b".into());
+                let c = || box UExpressionInner::Identifier("c".into()).annotate(UBitwidth::B32);
+
+                let expressions = vec![
+                    (BooleanExpression::FieldEq(a(), a()), true),
+                    (BooleanExpression::BoolEq(b(), b()), true),
+                    (BooleanExpression::UintEq(c(), c()), true),
+                    (BooleanExpression::UintLt(c(), c()), false),
+                    (BooleanExpression::UintLe(c(), c()), true),
+                    (BooleanExpression::UintGt(c(), c()), false),
+                    (BooleanExpression::UintGe(c(), c()), true),
+                ];
+
+                for (e, expected) in expressions {
+                    assert_eq!(
+                        Propagator::with_constants(&mut Constants::new())
+                            .fold_boolean_expression(e),
+                        Ok(BooleanExpression::Value(expected))
+                    );
+                }
+            }
+
+            #[test]
+            fn identical_operands_with_constraints() {
+                let a = || box FieldElementExpression::<Bn128Field>::Identifier("a".into());
+                let quotient = || {
+                    box FieldElementExpression::Div(
+                        a(),
+                        box FieldElementExpression::Identifier("b".into()),
+                    )
+                };
+                let c = || {
+                    box UExpressionInner::Div(
+                        box UExpressionInner::Identifier("c".into()).annotate(UBitwidth::B32),
+                        box UExpressionInner::Identifier("d".into()).annotate(UBitwidth::B32),
+                    )
+                    .annotate(UBitwidth::B32)
+                };
+
+                // field comparisons check that their operands are in range, and divisions that their divisor
+                // is nonzero, so these comparisons are kept
+                let expressions = vec![
+                    BooleanExpression::FieldLt(a(), a()),
+                    BooleanExpression::FieldLe(a(), a()),
+                    BooleanExpression::FieldGt(a(), a()),
+                    BooleanExpression::FieldGe(a(), a()),
+                    BooleanExpression::FieldEq(quotient(), quotient()),
+                    BooleanExpression::UintEq(c(), c()),
+                    BooleanExpression::UintLt(c(), c()),
+                ];
+
+                for e in expressions {
+                    assert_eq!(
+                        Propagator::with_constants(&mut Constants::new())
+                            .fold_boolean_expression(e.clone()),
+                        Ok(e)
+                    );
+                }
+            }
+
             #[test]
             fn and() {
                 let a_bool: Identifier = "a".into();
@@ -1875,5 +2527,376 @@ mod tests {
                 );
             }
         }
+
+        #[cfg(test)]
+        mod uint {
+            use super::*;
+
+            #[test]
+            fn arithmetic() {
+                // ((10 - 3) * 4 + 1) / 2 % 5 == 4
+                let e = UExpressionInner::Rem(
+                    box UExpressionInner::Div(
+                        box UExpressionInner::Add(
+                            box UExpressionInner::Mult(
+                                box UExpressionInner::Sub(box 10u32.into(), box 3u32.into())
+                                    .annotate(UBitwidth::B32),
+                                box 4u32.into(),
+                            )
+                            .annotate(UBitwidth::B32),
+                            box 1u32.into(),
+                        )
+                        .annotate(UBitwidth::B32),
+                        box 2u32.into(),
+                    )
+                    .annotate(UBitwidth::B32),
+                    box 5u32.into(),
+                )
+                .annotate(UBitwidth::B32);
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_uint_expression(e),
+                    Ok(UExpression::from(4u32))
+                );
+            }
+
+            #[test]
+            fn arithmetic_wraps() {
+                // 0u8 - 1 == 255
+                // 200u8 + 100 == 44
+                // 16u8 * 17 == 16
+                let value = |v: u128| UExpressionInner::Value(v).annotate(UBitwidth::B8);
+
+                let cases = vec![
+                    (UExpressionInner::Sub(box value(0), box value(1)), 255),
+                    (UExpressionInner::Add(box value(200), box value(100)), 44),
+                    (UExpressionInner::Mult(box value(16), box value(17)), 16),
+                ];
+
+                for (e, expected) in cases {
+                    assert_eq!(
+                        Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                            .fold_uint_expression(e.annotate(UBitwidth::B8)),
+                        Ok(value(expected))
+                    );
+                }
+            }
+
+            #[test]
+            fn division_by_zero() {
+                // a / (2 - 2)
+                let e = UExpressionInner::Div(
+                    box UExpressionInner::Identifier("a".into()).annotate(UBitwidth::B32),
+                    box UExpressionInner::Sub(box 2u32.into(), box 2u32.into())
+                        .annotate(UBitwidth::B32),
+                )
+                .annotate(UBitwidth::B32);
+
+                assert!(matches!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_uint_expression(e),
+                    Err(Error::DivisionByZero(..))
+                ));
+            }
+
+            #[test]
+            fn rotate_left() {
+                // rotl(0x80000000u32, 1) == 1
+                let e = UExpression::from(0x80000000u32).rotate_left(1);
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_uint_expression(e),
+                    Ok(UExpression::from(1u32))
+                );
+            }
+
+            #[test]
+            fn rotate_right() {
+                // rotr(0x1u32, 1) == 0x80000000
+                let e = UExpression::from(1u32).rotate_right(1);
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_uint_expression(e),
+                    Ok(UExpression::from(0x80000000u32))
+                );
+            }
+
+            #[test]
+            fn rotate_identity() {
+                // rotl(a, 0) == a
+                // rotl(a, 32) == a
+                let a = UExpressionInner::Identifier("a".into()).annotate(UBitwidth::B32);
+
+                for &by in &[0, 32] {
+                    assert_eq!(
+                        Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                            .fold_uint_expression(a.clone().rotate_left(by)),
+                        Ok(a.clone())
+                    );
+                }
+            }
+
+            #[test]
+            fn rotate_from_shifts() {
+                // (a << 1) | (a >> 31) == rotl(a, 1)
+                let a = UExpressionInner::Identifier("a".into()).annotate(UBitwidth::B32);
+
+                let e = UExpressionInner::Or(
+                    box a.clone().left_shift(1u32.into()),
+                    box a.clone().right_shift(31u32.into()),
+                )
+                .annotate(UBitwidth::B32);
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_uint_expression(e),
+                    Ok(a.rotate_left(1))
+                );
+            }
+        }
+
+        #[cfg(test)]
+        mod array {
+            use super::*;
+
+            #[test]
+            fn array_eq() {
+                let constant = |values: Vec<usize>| {
+                    let size = values.len() as u32;
+                    ArrayExpressionInner::Value(
+                        values
+                            .into_iter()
+                            .map(|v| FieldElementExpression::Number(Bn128Field::from(v)).into())
+                            .collect::<Vec<_>>()
+                            .into(),
+                    )
+                    .annotate(Type::FieldElement, size)
+                };
+                let a =
+                    ArrayExpressionInner::Identifier("a".into()).annotate(Type::FieldElement, 2u32);
+                let b =
+                    ArrayExpressionInner::Identifier("b".into()).annotate(Type::FieldElement, 2u32);
+
+                // both constant
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_boolean_expression(BooleanExpression::ArrayEq(
+                            box constant(vec![1, 2, 3]),
+                            box constant(vec![1, 2, 3])
+                        )),
+                    Ok(BooleanExpression::Value(true))
+                );
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_boolean_expression(BooleanExpression::ArrayEq(
+                            box constant(vec![1, 2, 3]),
+                            box constant(vec![1, 2, 4])
+                        )),
+                    Ok(BooleanExpression::Value(false))
+                );
+
+                // constant right operand: a == [1, 2] is a[0] == 1 && a[1] == 2
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_boolean_expression(BooleanExpression::ArrayEq(
+                            box a.clone(),
+                            box constant(vec![1, 2])
+                        )),
+                    Ok(BooleanExpression::And(
+                        box BooleanExpression::FieldEq(
+                            box FieldElementExpression::select(a.clone(), 0u32),
+                            box FieldElementExpression::Number(Bn128Field::from(1))
+                        ),
+                        box BooleanExpression::FieldEq(
+                            box FieldElementExpression::select(a.clone(), 1u32),
+                            box FieldElementExpression::Number(Bn128Field::from(2))
+                        )
+                    ))
+                );
+
+                // the element-wise equalities are grouped in a balanced tree:
+                // c == [1, 2, 3, 4] is (c[0] == 1 && c[1] == 2) && (c[2] == 3 && c[3] == 4)
+                let c =
+                    ArrayExpressionInner::Identifier("c".into()).annotate(Type::FieldElement, 4u32);
+                let eq = |i: u32| {
+                    BooleanExpression::FieldEq(
+                        box FieldElementExpression::select(c.clone(), i),
+                        box FieldElementExpression::Number(Bn128Field::from(i + 1)),
+                    )
+                };
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_boolean_expression(BooleanExpression::ArrayEq(
+                            box c.clone(),
+                            box constant(vec![1, 2, 3, 4])
+                        )),
+                    Ok(BooleanExpression::And(
+                        box BooleanExpression::And(box eq(0), box eq(1)),
+                        box BooleanExpression::And(box eq(2), box eq(3))
+                    ))
+                );
+
+                // both runtime
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_boolean_expression(BooleanExpression::ArrayEq(
+                            box a.clone(),
+                            box b.clone()
+                        )),
+                    Ok(BooleanExpression::ArrayEq(box a, box b))
+                );
+            }
+
+            #[test]
+            fn select_in_if_else() {
+                // (if c then [1, 2] else [3, 4])[0] folds to `if c then 1 else 3`
+
+                let array = |values: Vec<usize>| {
+                    ArrayExpressionInner::Value(
+                        values
+                            .into_iter()
+                            .map(|v| FieldElementExpression::Number(Bn128Field::from(v)).into())
+                            .collect::<Vec<_>>()
+                            .into(),
+                    )
+                    .annotate(Type::FieldElement, 2u32)
+                };
+
+                let e = FieldElementExpression::select(
+                    ArrayExpression::if_else(
+                        BooleanExpression::Identifier("c".into()),
+                        array(vec![1, 2]),
+                        array(vec![3, 4]),
+                    ),
+                    0u32,
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_field_expression(e),
+                    Ok(FieldElementExpression::if_else(
+                        BooleanExpression::Identifier("c".into()),
+                        FieldElementExpression::Number(Bn128Field::from(1)),
+                        FieldElementExpression::Number(Bn128Field::from(3)),
+                    ))
+                );
+            }
+
+            #[test]
+            fn slice_of_concatenation() {
+                // [...a, ...b][0..2] with `a` of size 2 folds to `a`
+
+                let a =
+                    ArrayExpressionInner::Identifier("a".into()).annotate(Type::FieldElement, 2u32);
+                let b =
+                    ArrayExpressionInner::Identifier("b".into()).annotate(Type::FieldElement, 3u32);
+
+                let concatenation = ArrayExpressionInner::Value(
+                    vec![
+                        TypedExpressionOrSpread::Spread(a.clone().into()),
+                        TypedExpressionOrSpread::Spread(b.into()),
+                    ]
+                    .into(),
+                )
+                .annotate(Type::FieldElement, 5u32);
+
+                let e = ArrayExpressionInner::Slice(
+                    box concatenation.clone(),
+                    box 0u32.into(),
+                    box 2u32.into(),
+                )
+                .annotate(Type::FieldElement, 2u32);
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_array_expression(e),
+                    Ok(a)
+                );
+
+                // a slice which does not match a single spread is left untouched
+
+                let e = ArrayExpressionInner::Slice(
+                    box concatenation,
+                    box 1u32.into(),
+                    box 3u32.into(),
+                )
+                .annotate(Type::FieldElement, 2u32);
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_array_expression(e.clone()),
+                    Ok(e)
+                );
+            }
+        }
+
+        mod structs {
+            use super::*;
+            use crate::typed_absy::types::StructMember;
+
+            #[test]
+            fn nested_member() {
+                // struct Inner { field a; field b }
+                // struct Outer { Inner inner; field c }
+                // Outer { inner: Inner { a: 1, b: x }, c: y }.inner.a folds to `1`
+                // Outer { inner: Inner { a: 1, b: x }, c: y }.inner.b folds to `x`
+
+                let inner_ty = StructType::new(
+                    "".into(),
+                    "Inner".into(),
+                    vec![
+                        StructMember::new("a".into(), Type::FieldElement),
+                        StructMember::new("b".into(), Type::FieldElement),
+                    ],
+                );
+
+                let outer_ty = StructType::new(
+                    "".into(),
+                    "Outer".into(),
+                    vec![
+                        StructMember::new("inner".into(), Type::Struct(inner_ty.clone())),
+                        StructMember::new("c".into(), Type::FieldElement),
+                    ],
+                );
+
+                let outer = StructExpressionInner::Value(vec![
+                    StructExpressionInner::Value(vec![
+                        FieldElementExpression::Number(Bn128Field::from(1)).into(),
+                        FieldElementExpression::Identifier("x".into()).into(),
+                    ])
+                    .annotate(inner_ty)
+                    .into(),
+                    FieldElementExpression::Identifier("y".into()).into(),
+                ])
+                .annotate(outer_ty);
+
+                let e = FieldElementExpression::member(
+                    StructExpression::member(outer.clone(), "inner".into()),
+                    "a".into(),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_field_expression(e),
+                    Ok(FieldElementExpression::Number(Bn128Field::from(1)))
+                );
+
+                // a non-constant member only blocks folding to a literal for its own path
+
+                let e = FieldElementExpression::member(
+                    StructExpression::member(outer, "inner".into()),
+                    "b".into(),
+                );
+
+                assert_eq!(
+                    Propagator::<Bn128Field>::with_constants(&mut Constants::new())
+                        .fold_field_expression(e),
+                    Ok(FieldElementExpression::Identifier("x".into()))
+                );
+            }
+        }
     }
 }
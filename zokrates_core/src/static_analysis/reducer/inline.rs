@@ -26,9 +26,12 @@
 // - The return value(s) are assigned to internal variables
 
 use crate::embed::FlatEmbed;
+use crate::static_analysis::propagation::is_constant;
 use crate::static_analysis::reducer::Output;
 use crate::static_analysis::reducer::ShallowTransformer;
 use crate::static_analysis::reducer::Versions;
+use crate::static_analysis::Propagator;
+use crate::typed_absy::result_folder::ResultFolder;
 use crate::typed_absy::types::{ConcreteGenericsAssignment, IntoTypes};
 use crate::typed_absy::CoreIdentifier;
 use crate::typed_absy::Identifier;
@@ -38,10 +41,14 @@ use crate::typed_absy::{
     Signature, TypedExpression, TypedFunctionSymbol, TypedProgram, TypedStatement, Types,
     UExpression, UExpressionInner, Variable,
 };
+use std::collections::HashMap;
 use zokrates_field::Field;
 
 pub enum InlineError<'ast, T> {
     Generic(DeclarationFunctionKey<'ast>, ConcreteFunctionKey<'ast>),
+    GenericArity(DeclarationFunctionKey<'ast>, usize),
+    GenericShadowed(DeclarationFunctionKey<'ast>, &'ast str),
+    Recursion(DeclarationFunctionKey<'ast>),
     Flat(
         FlatEmbed,
         Vec<u32>,
@@ -74,6 +81,89 @@ fn get_canonical_function<'ast, T: Field>(
     }
 }
 
+// whether `function_key` resolves to a function defined in the program, as opposed to an embed
+pub fn is_defined<'ast, T: Field>(
+    function_key: &DeclarationFunctionKey<'ast>,
+    program: &TypedProgram<'ast, T>,
+) -> bool {
+    matches!(
+        get_canonical_function(function_key.clone(), program),
+        (_, TypedFunctionSymbol::Here(_))
+    )
+}
+
+// the values returned by a non-generic function whose body only returns constants, in which case inlining it is not required
+pub fn constant_output<'ast, T: Field>(
+    function_key: &DeclarationFunctionKey<'ast>,
+    program: &TypedProgram<'ast, T>,
+) -> Option<Vec<TypedExpression<'ast, T>>> {
+    let f = match get_canonical_function(function_key.clone(), program) {
+        (_, TypedFunctionSymbol::Here(f)) if f.signature.generics.is_empty() => f,
+        _ => return None,
+    };
+
+    match &f.statements[..] {
+        [TypedStatement::Return(expressions)] => expressions
+            .iter()
+            .map(|e| {
+                Propagator::with_constants(&mut HashMap::new())
+                    .fold_expression(e.clone())
+                    .ok()
+                    .filter(is_constant)
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+// the outputs of an inlined call if they are all constant once its body is propagated, in which case the body is not required
+pub fn constant_call_output<'ast, T: Field>(
+    statements: &[TypedStatement<'ast, T>],
+    expressions: &[TypedExpression<'ast, T>],
+) -> Option<Vec<TypedExpression<'ast, T>>> {
+    let mut constants = HashMap::new();
+    let mut propagator = Propagator::with_constants(&mut constants);
+
+    for s in statements {
+        let remaining = propagator.fold_statement(s.clone()).ok()?;
+
+        if !remaining.iter().all(|s| {
+            matches!(
+                s,
+                TypedStatement::PushCallLog(..) | TypedStatement::PopCallLog
+            )
+        }) {
+            return None;
+        }
+    }
+
+    expressions
+        .iter()
+        .map(|e| {
+            propagator
+                .fold_expression(e.clone())
+                .ok()
+                .filter(is_constant)
+        })
+        .collect()
+}
+
+// the first generic parameter which is redeclared as a variable in `statements`, as substituting it would also replace the variable
+fn shadowed_generic<'ast, T>(
+    statements: &[TypedStatement<'ast, T>],
+    generics: &ConcreteGenericsAssignment<'ast>,
+) -> Option<&'ast str> {
+    statements.iter().find_map(|s| match s {
+        TypedStatement::Declaration(v) => generics
+            .0
+            .keys()
+            .find(|g| v.id.id == CoreIdentifier::Source(g.name))
+            .map(|g| g.name),
+        TypedStatement::For(_, _, _, statements) => shadowed_generic(statements, generics),
+        _ => None,
+    })
+}
+
 type InlineResult<'ast, T> = Result<
     Output<(Vec<TypedStatement<'ast, T>>, Vec<TypedExpression<'ast, T>>), Vec<Versions<'ast>>>,
     InlineError<'ast, T>,
@@ -86,11 +176,17 @@ pub fn inline_call<'a, 'ast, T: Field, E: Expr<'ast, T>>(
     output: &E::Ty,
     program: &TypedProgram<'ast, T>,
     versions: &'a mut Versions<'ast>,
+    call_stack: &[DeclarationFunctionKey<'ast>],
 ) -> InlineResult<'ast, T> {
     use std::convert::TryFrom;
 
     use crate::typed_absy::Typed;
 
+    // the number of generics provided must match the declaration before we try to monomorphize
+    if generics.len() != k.signature.generics.len() {
+        return Err(InlineError::GenericArity(k, generics.len()));
+    }
+
     let output_types = output.clone().into_types();
 
     // we try to get concrete values for explicit generics
@@ -136,6 +232,11 @@ pub fn inline_call<'a, 'ast, T: Field, E: Expr<'ast, T>>(
 
     let (decl_key, symbol) = get_canonical_function(k.clone(), program);
 
+    // a function which is already being inlined would be inlined forever
+    if call_stack.contains(&decl_key) {
+        return Err(InlineError::Recursion(decl_key));
+    }
+
     // get an assignment of generics for this call site
     let assignment: ConcreteGenericsAssignment<'ast> = k
         .signature
@@ -164,6 +265,10 @@ pub fn inline_call<'a, 'ast, T: Field, E: Expr<'ast, T>>(
 
     assert_eq!(f.arguments.len(), arguments.len());
 
+    if let Some(name) = shadowed_generic(&f.statements, &assignment) {
+        return Err(InlineError::GenericShadowed(decl_key, name));
+    }
+
     let (ssa_f, incomplete_data) = match ShallowTransformer::transform(f, &assignment, versions) {
         Output::Complete(v) => (v, None),
         Output::Incomplete(statements, for_loop_versions) => (statements, Some(for_loop_versions)),
@@ -13,30 +13,46 @@
 
 mod inline;
 mod shallow_ssa;
+mod unused_booleans;
+mod unused_generics;
 
-use self::inline::{inline_call, InlineError};
+use self::inline::{constant_call_output, constant_output, inline_call, is_defined, InlineError};
+use self::unused_booleans::UnusedBooleansDetector;
+use self::unused_generics::UnusedGenericsDetector;
+use crate::typed_absy::folder;
 use crate::typed_absy::result_folder::*;
 use crate::typed_absy::types::ConcreteGenericsAssignment;
+use crate::typed_absy::types::DeclarationConstant;
 use crate::typed_absy::types::GGenericsAssignment;
 use crate::typed_absy::Folder;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 use crate::typed_absy::{
-    ArrayExpressionInner, ArrayType, BlockExpression, CoreIdentifier, Expr, FunctionCall,
-    FunctionCallExpression, FunctionCallOrExpression, Id, Identifier, TypedExpression,
-    TypedExpressionList, TypedExpressionListInner, TypedFunction, TypedFunctionSymbol, TypedModule,
-    TypedProgram, TypedStatement, UExpression, UExpressionInner, Variable,
+    ArrayExpressionInner, ArrayType, BlockExpression, BooleanExpression, CoreIdentifier,
+    DeclarationFunctionKey, DeclarationSignature, Expr, FieldElementExpression, FunctionCall,
+    FunctionCallExpression, FunctionCallOrExpression, Id, Identifier, TypedAssignee,
+    TypedExpression, TypedExpressionList, TypedExpressionListInner, TypedExpressionOrSpread,
+    TypedFunction, TypedFunctionSymbol, TypedModule, TypedModules, TypedProgram, TypedStatement,
+    UExpression, UExpressionInner, Variable,
 };
 
 use zokrates_field::Field;
 
 use self::shallow_ssa::ShallowTransformer;
 
+use crate::static_analysis::dead_code::has_effects;
+use crate::static_analysis::propagation::{self, is_constant};
 use crate::static_analysis::Propagator;
 
 use std::fmt;
 
-const MAX_FOR_LOOP_SIZE: u128 = 2u128.pow(20);
+// the default maximum number of iterations of a loop
+pub const MAX_FOR_LOOP_SIZE: usize = 2usize.pow(20);
+// the default number of iterations of a loop above which a warning is emitted
+pub const LARGE_FOR_LOOP_SIZE: usize = 1000;
+// constant array literals larger than this trigger a warning when duplicated by loop unrolling
+const LARGE_CONSTANT_ARRAY_SIZE: usize = 100;
 
 // An SSA version map, giving access to the latest version number for each identifier
 pub type Versions<'ast> = HashMap<CoreIdentifier<'ast>, usize>;
@@ -54,7 +70,14 @@ pub enum Error {
     GenericsInMain,
     // TODO: give more details about what's blocking the progress
     NoProgress,
-    LoopTooLarge(u128),
+    UnresolvableSize(String),
+    LoopTooLarge(u128, usize),
+    UnsatisfiableAssertion(u128, String),
+    GenericArityMismatch(String, usize, usize),
+    TooManyInstantiations(usize),
+    GenericShadowedByVariable(String, String),
+    Recursion(String),
+    DidNotConverge(usize),
 }
 
 impl fmt::Display for Error {
@@ -67,7 +90,134 @@ impl fmt::Display for Error {
             ),
             Error::GenericsInMain => write!(f, "Cannot generate code for generic function"),
             Error::NoProgress => write!(f, "Failed to unroll or inline program. Check that main function arguments aren't used as array size or for-loop bounds"),
-            Error::LoopTooLarge(size) => write!(f, "Found a loop of size {}, which is larger than the maximum allowed of {}. Check the loop bounds, especially for underflows", size, MAX_FOR_LOOP_SIZE),
+            Error::UnresolvableSize(size) => write!(f, "Failed to reduce the size `{}` to a constant. Check that main function arguments aren't used as array sizes or generic parameters", size),
+            Error::LoopTooLarge(size, max) => write!(f, "Found a loop of size {}, which is larger than the maximum allowed of {}. Check the loop bounds, especially for underflows", size, max),
+            Error::UnsatisfiableAssertion(index, message) => write!(f, "{} in loop iteration {}", message, index),
+            Error::GenericArityMismatch(key, expected, found) => write!(f, "Function `{}` expects {} generic parameters, found {}", key, expected, found),
+            Error::TooManyInstantiations(max) => write!(f, "Found more than the maximum allowed of {} distinct instantiations of generic functions. Check the generic parameters of calls inside loops", max),
+            Error::GenericShadowedByVariable(key, name) => write!(f, "Generic parameter `{}` of function `{}` is shadowed by a variable", name, key),
+            Error::Recursion(key) => write!(f, "Function `{}` calls itself, directly or through other functions, which is not supported", key),
+            Error::DidNotConverge(max) => write!(f, "Failed to reduce the program in the maximum allowed of {} rounds", max),
+        }
+    }
+}
+
+// Which function calls are inlined
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InlinePolicy {
+    // inline all calls
+    Always,
+    // only inline calls to generic functions and embeds, keeping the other functions in the program
+    // the resulting program is meant to be inspected, as the following passes expect all calls to be inlined
+    NeverForNonGeneric,
+}
+
+impl Default for InlinePolicy {
+    fn default() -> Self {
+        InlinePolicy::Always
+    }
+}
+
+// Options of the reduction
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReducerConfig {
+    // the maximum number of distinct instantiations of generic functions, if any
+    pub max_instantiations: Option<usize>,
+    // the maximum number of iterations of a loop
+    pub max_loop_iterations: usize,
+    // the maximum number of rounds of inlining and unrolling in a function, if any
+    pub max_rounds: Option<usize>,
+    // the number of iterations of a loop above which a warning is emitted
+    pub large_loop_iterations: usize,
+    // which function calls are inlined
+    pub inline_policy: InlinePolicy,
+    // whether a public input of `main` asserted to be equal to another one is merged into it
+    pub merge_public_inputs: bool,
+}
+
+impl Default for ReducerConfig {
+    fn default() -> Self {
+        ReducerConfig {
+            max_instantiations: None,
+            max_loop_iterations: MAX_FOR_LOOP_SIZE,
+            max_rounds: None,
+            large_loop_iterations: LARGE_FOR_LOOP_SIZE,
+            inline_policy: InlinePolicy::default(),
+            merge_public_inputs: false,
+        }
+    }
+}
+
+// The outcome of a best-effort reduction: the reduced program, or the functions which could be reduced and the error
+#[derive(Debug, PartialEq)]
+pub struct ReduceOutcome<'ast, T> {
+    pub program: TypedProgram<'ast, T>,
+    pub error: Option<Error>,
+}
+
+// Statistics about the reduced program
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReductionStats {
+    // the number of statements in the reduced `main` function
+    pub statement_count: usize,
+    // the number of function calls which were inlined
+    pub inlined_calls: usize,
+    // the maximum number of nested inlined calls
+    pub max_call_depth: usize,
+    // the number of loop iterations which were generated
+    pub unrolled_iterations: u128,
+}
+
+// Non-fatal issues found while reducing the program
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Warning {
+    UnusedGeneric { function: String, generic: String },
+    DuplicatedConstantArray { size: usize, iterations: u128 },
+    EmptyLoop { variable: String, iterations: u128 },
+    DuplicatedPublicInput { first: String, second: String },
+    MergedPublicInput { kept: String, removed: String },
+    UnusedBoolean { function: String, variable: String },
+    LargeLoop { variable: String, iterations: u128 },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::UnusedGeneric { function, generic } => write!(
+                f,
+                "Generic parameter {} of function {} is never used. Consider removing it",
+                generic, function
+            ),
+            Warning::DuplicatedConstantArray { size, iterations } => write!(
+                f,
+                "Found a constant array of size {} in the body of a loop, which gets duplicated in each of its {} iterations. Consider defining it before the loop",
+                size, iterations
+            ),
+            Warning::EmptyLoop { variable, iterations } => write!(
+                f,
+                "Found a loop over {} with {} iterations and an empty body. Did you forget to write it?",
+                variable, iterations
+            ),
+            Warning::DuplicatedPublicInput { first, second } => write!(
+                f,
+                "Public inputs {} and {} are asserted to be equal, which wastes a public input. Consider removing one of them",
+                first, second
+            ),
+            Warning::MergedPublicInput { kept, removed } => write!(
+                f,
+                "Public input {} is asserted to be equal to {}, so it was merged into it and removed from the public inputs",
+                removed, kept
+            ),
+            Warning::UnusedBoolean { function, variable } => write!(
+                f,
+                "Boolean {} of function {} is computed but never used. Did you forget to assert it?",
+                variable, function
+            ),
+            Warning::LargeLoop { variable, iterations } => write!(
+                f,
+                "Found a loop over {} with {} iterations, each of which duplicates its body. Check that this is intended",
+                variable, iterations
+            ),
         }
     }
 }
@@ -166,6 +316,12 @@ struct Reducer<'ast, 'a, T> {
     program: &'a TypedProgram<'ast, T>,
     versions: &'a mut Versions<'ast>,
     substitutions: &'a mut Substitutions<'ast>,
+    warnings: &'a mut Vec<Warning>,
+    // the functions whose inlined body is being visited, innermost last
+    call_stack: Vec<DeclarationFunctionKey<'ast>>,
+    config: &'a ReducerConfig,
+    // the number of loop iterations generated in this pass
+    unrolled_iterations: u128,
     complete: bool,
 }
 
@@ -174,7 +330,9 @@ impl<'ast, 'a, T: Field> Reducer<'ast, 'a, T> {
         program: &'a TypedProgram<'ast, T>,
         versions: &'a mut Versions<'ast>,
         substitutions: &'a mut Substitutions<'ast>,
+        warnings: &'a mut Vec<Warning>,
         for_loop_versions: Vec<Versions<'ast>>,
+        config: &'a ReducerConfig,
     ) -> Self {
         // we reverse the vector as it's cheaper to `pop` than to take from
         // the head
@@ -189,9 +347,20 @@ impl<'ast, 'a, T: Field> Reducer<'ast, 'a, T> {
             substitutions,
             program,
             versions,
+            warnings,
+            call_stack: vec![],
+            config,
+            unrolled_iterations: 0,
             complete: true,
         }
     }
+
+    // whether a call to `key` is kept rather than inlined under the inlining policy
+    fn keeps_call(&self, key: &DeclarationFunctionKey<'ast>) -> bool {
+        self.config.inline_policy == InlinePolicy::NeverForNonGeneric
+            && key.signature.generics.is_empty()
+            && is_defined(key, self.program)
+    }
 }
 
 impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
@@ -216,6 +385,23 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
             .map(|e| self.fold_expression(e))
             .collect::<Result<_, _>>()?;
 
+        if self.keeps_call(&e.function_key) {
+            return Ok(FunctionCallOrExpression::Expression(E::function_call(
+                e.function_key,
+                generics,
+                arguments,
+            )));
+        }
+
+        // the arguments are not kept, so this only applies when computing them does not constrain the program
+        if arguments.iter().all(|a| !has_effects(a.clone())) {
+            if let Some(mut values) = constant_output(&e.function_key, &self.program) {
+                return Ok(FunctionCallOrExpression::Expression(
+                    E::from(values.pop().unwrap()).into_inner(),
+                ));
+            }
+        }
+
         let res = inline_call::<_, E>(
             e.function_key.clone(),
             generics,
@@ -223,6 +409,7 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
             ty,
             &self.program,
             &mut self.versions,
+            &self.call_stack,
         );
 
         match res {
@@ -246,6 +433,16 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
                 conc.to_string(),
                 decl.to_string()
             ))),
+            Err(InlineError::GenericArity(decl, count)) => Err(Error::GenericArityMismatch(
+                decl.to_string(),
+                decl.signature.generics.len(),
+                count,
+            )),
+            Err(InlineError::GenericShadowed(decl, name)) => Err(Error::GenericShadowedByVariable(
+                decl.to_string(),
+                name.to_string(),
+            )),
+            Err(InlineError::Recursion(decl)) => Err(Error::Recursion(decl.to_string())),
             Err(InlineError::NonConstant(key, generics, arguments, _)) => {
                 self.complete = false;
 
@@ -328,6 +525,42 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
                     .map(|a| self.fold_expression(a))
                     .collect::<Result<_, _>>()?;
 
+                if self.keeps_call(&function_call.function_key) {
+                    return Ok(self
+                        .statement_buffer
+                        .drain(..)
+                        .chain(std::iter::once(TypedStatement::MultipleDefinition(
+                            v,
+                            TypedExpressionList::function_call(
+                                function_call.function_key,
+                                generics,
+                                arguments,
+                            )
+                            .annotate(types),
+                        )))
+                        .collect());
+                }
+
+                // a function which returns constants is not inlined, its outputs are used directly
+                // the arguments are not kept, so this only applies when computing them does not constrain the program
+                if arguments.iter().all(|a| !has_effects(a.clone())) {
+                    if let Some(values) =
+                        constant_output(&function_call.function_key, &self.program)
+                    {
+                        return Ok(self
+                            .statement_buffer
+                            .drain(..)
+                            .chain(
+                                v.into_iter()
+                                    .zip(values)
+                                    .map(|(v, e)| TypedStatement::Definition(v, e)),
+                            )
+                            .collect());
+                    }
+                }
+
+                let constant_arguments = arguments.iter().all(is_constant);
+
                 match inline_call::<_, TypedExpressionList<'ast, T>>(
                     function_call.function_key,
                     generics,
@@ -335,12 +568,28 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
                     &types,
                     &self.program,
                     &mut self.versions,
+                    &self.call_stack,
                 ) {
                     Ok(Output::Complete((statements, expressions))) => {
                         assert_eq!(v.len(), expressions.len());
 
                         self.complete &= true;
 
+                        // a call with constant arguments whose outputs are all constant is replaced by its outputs
+                        if constant_arguments {
+                            if let Some(values) = constant_call_output(&statements, &expressions) {
+                                return Ok(self
+                                    .statement_buffer
+                                    .drain(..)
+                                    .chain(
+                                        v.into_iter()
+                                            .zip(values)
+                                            .map(|(v, e)| TypedStatement::Definition(v, e)),
+                                    )
+                                    .collect());
+                            }
+                        }
+
                         Ok(statements
                             .into_iter()
                             .chain(
@@ -370,6 +619,17 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
                         conc.to_string(),
                         decl.to_string()
                     ))),
+                    Err(InlineError::GenericArity(decl, count)) => {
+                        Err(Error::GenericArityMismatch(
+                            decl.to_string(),
+                            decl.signature.generics.len(),
+                            count,
+                        ))
+                    }
+                    Err(InlineError::GenericShadowed(decl, name)) => Err(
+                        Error::GenericShadowedByVariable(decl.to_string(), name.to_string()),
+                    ),
+                    Err(InlineError::Recursion(decl)) => Err(Error::Recursion(decl.to_string())),
                     Err(InlineError::NonConstant(key, generics, arguments, output_types)) => {
                         self.complete = false;
 
@@ -410,11 +670,45 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
 
                         let mut transformer = ShallowTransformer::with_versions(&mut self.versions);
 
-                        if to - from > MAX_FOR_LOOP_SIZE {
-                            return Err(Error::LoopTooLarge(to.saturating_sub(*from)));
+                        // a range whose end is before its start, for example `1..N` with `N == 0`, is empty
+                        let iterations = to.saturating_sub(*from);
+
+                        if iterations > self.config.max_loop_iterations as u128 {
+                            return Err(Error::LoopTooLarge(
+                                iterations,
+                                self.config.max_loop_iterations,
+                            ));
+                        }
+
+                        if iterations > self.config.large_loop_iterations as u128 {
+                            self.warnings.push(Warning::LargeLoop {
+                                variable: v.id.id.to_string(),
+                                iterations,
+                            });
+                        }
+
+                        if iterations > 0 && statements.is_empty() {
+                            self.warnings.push(Warning::EmptyLoop {
+                                variable: v.id.id.to_string(),
+                                iterations,
+                            });
+                        }
+
+                        if iterations > 1 {
+                            self.warnings.extend(
+                                LargeConstantArrayFinder::find(&statements).into_iter().map(
+                                    |size| Warning::DuplicatedConstantArray { size, iterations },
+                                ),
+                            );
                         }
 
-                        for index in *from..*to {
+                        // an accumulation which does not depend on the loop is computed in a single iteration
+                        let (to, statements) = match accumulation(&v, &statements, iterations) {
+                            Some(statement) => (*from + 1, vec![statement]),
+                            None => (*to, statements),
+                        };
+
+                        for index in *from..to {
                             let statements: Vec<TypedStatement<_>> =
                                 std::iter::once(TypedStatement::Definition(
                                     v.clone().into(),
@@ -425,9 +719,15 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
                                 .flatten()
                                 .collect();
 
+                            if let Some(message) = find_unsatisfiable_assertion(&statements) {
+                                return Err(Error::UnsatisfiableAssertion(index, message));
+                            }
+
                             out_statements.extend(statements);
                         }
 
+                        self.unrolled_iterations += to.saturating_sub(*from);
+
                         let backups = transformer.for_loop_backups;
                         let blocked = transformer.blocked;
 
@@ -454,6 +754,14 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
                     }
                 }
             }
+            TypedStatement::PushCallLog(key, generics) => {
+                self.call_stack.push(key.clone());
+                Ok(vec![TypedStatement::PushCallLog(key, generics)])
+            }
+            TypedStatement::PopCallLog => {
+                self.call_stack.pop();
+                Ok(vec![TypedStatement::PopCallLog])
+            }
             s => fold_statement(self, s),
         };
 
@@ -486,7 +794,161 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
     }
 }
 
-pub fn reduce_program<T: Field>(p: TypedProgram<T>) -> Result<TypedProgram<T>, Error> {
+// Find the constant array literals larger than `LARGE_CONSTANT_ARRAY_SIZE`, returning their sizes
+#[derive(Default)]
+struct LargeConstantArrayFinder {
+    sizes: Vec<usize>,
+}
+
+impl LargeConstantArrayFinder {
+    fn find<T: Field>(statements: &[TypedStatement<T>]) -> Vec<usize> {
+        let mut finder = Self::default();
+
+        for s in statements {
+            finder.fold_statement(s.clone());
+        }
+
+        finder.sizes
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for LargeConstantArrayFinder {
+    fn fold_array_expression_inner(
+        &mut self,
+        ty: &ArrayType<'ast, T>,
+        e: ArrayExpressionInner<'ast, T>,
+    ) -> ArrayExpressionInner<'ast, T> {
+        match e {
+            ArrayExpressionInner::Value(v)
+                if v.0.len() > LARGE_CONSTANT_ARRAY_SIZE
+                    && v.0.iter().all(|e| match e {
+                        TypedExpressionOrSpread::Expression(e) => is_constant(e),
+                        _ => false,
+                    }) =>
+            {
+                self.sizes.push(v.0.len());
+                ArrayExpressionInner::Value(v)
+            }
+            e => folder::fold_array_expression_inner(self, ty, e),
+        }
+    }
+}
+
+// Check whether an expression is the same in all iterations of a loop, in which the variables `variables` are redefined
+struct LoopInvariantChecker<'a, 'ast> {
+    variables: &'a [&'a Identifier<'ast>],
+    invariant: bool,
+}
+
+impl<'a, 'ast> LoopInvariantChecker<'a, 'ast> {
+    fn check<T: Field>(
+        e: &FieldElementExpression<'ast, T>,
+        variables: &'a [&'a Identifier<'ast>],
+    ) -> bool {
+        let mut checker = LoopInvariantChecker {
+            variables,
+            invariant: true,
+        };
+
+        checker.fold_field_expression(e.clone());
+
+        checker.invariant
+    }
+}
+
+impl<'a, 'ast, T: Field> Folder<'ast, T> for LoopInvariantChecker<'a, 'ast> {
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        if self.variables.contains(&&n) {
+            self.invariant = false;
+        }
+        n
+    }
+
+    // calls are inlined in each iteration, so we do not try to factor them
+    fn fold_function_call_expression<
+        E: Id<'ast, T> + From<TypedExpression<'ast, T>> + Expr<'ast, T> + FunctionCall<'ast, T>,
+    >(
+        &mut self,
+        _: &E::Ty,
+        e: FunctionCallExpression<'ast, T, E>,
+    ) -> FunctionCallOrExpression<'ast, T, E> {
+        self.invariant = false;
+        FunctionCallOrExpression::FunctionCall(e)
+    }
+}
+
+// rewrite the body of a loop `t = t + e` run `iterations` times, where `e` does not depend on the loop, to `t = t + iterations * e`
+fn accumulation<'ast, T: Field>(
+    index: &Variable<'ast, T>,
+    statements: &[TypedStatement<'ast, T>],
+    iterations: u128,
+) -> Option<TypedStatement<'ast, T>> {
+    if iterations < 2 {
+        return None;
+    }
+
+    match statements {
+        [TypedStatement::Definition(
+            TypedAssignee::Identifier(var),
+            TypedExpression::FieldElement(FieldElementExpression::Add(left, right)),
+        )] => match &**left {
+            FieldElementExpression::Identifier(id)
+                if *id == var.id && LoopInvariantChecker::check(right, &[&var.id, &index.id]) =>
+            {
+                Some(TypedStatement::Definition(
+                    TypedAssignee::Identifier(var.clone()),
+                    FieldElementExpression::Add(
+                        left.clone(),
+                        box FieldElementExpression::Mult(
+                            box FieldElementExpression::Number(T::from(iterations)),
+                            right.clone(),
+                        ),
+                    )
+                    .into(),
+                ))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn reduce_program<'ast, T: Field>(
+    p: TypedProgram<'ast, T>,
+    config: &ReducerConfig,
+) -> Result<TypedProgram<'ast, T>, Error> {
+    reduce_program_with_report(p, config).map(|(p, _, _)| p)
+}
+
+// Reduce a program, reporting statistics about the reduction and non-fatal issues alongside the reduced program
+pub fn reduce_program_with_report<'ast, T: Field>(
+    p: TypedProgram<'ast, T>,
+    config: &ReducerConfig,
+) -> Result<(TypedProgram<'ast, T>, ReductionStats, Vec<Warning>), Error> {
+    let mut warnings: Vec<_> = p
+        .modules
+        .values()
+        .flat_map(|m| m.functions.iter())
+        .filter_map(|(k, f)| match f {
+            TypedFunctionSymbol::Here(f) => Some((k, f)),
+            _ => None,
+        })
+        .flat_map(|(k, f)| {
+            UnusedGenericsDetector::detect(f)
+                .into_iter()
+                .map(move |g| Warning::UnusedGeneric {
+                    function: k.id.to_string(),
+                    generic: g.to_string(),
+                })
+                .chain(UnusedBooleansDetector::detect(f).into_iter().map(move |b| {
+                    Warning::UnusedBoolean {
+                        function: k.id.to_string(),
+                        variable: b.to_string(),
+                    }
+                }))
+        })
+        .collect();
+
     let main_module = p.modules.get(&p.main).unwrap().clone();
 
     let (main_key, main_function) = main_module
@@ -502,34 +964,217 @@ pub fn reduce_program<T: Field>(p: TypedProgram<T>) -> Result<TypedProgram<T>, E
 
     match main_function.signature.generics.len() {
         0 => {
-            let main_function = reduce_function(main_function, GGenericsAssignment::default(), &p)?;
+            let mut unrolled_iterations = 0;
+
+            let main_function = reduce_specialized_function(
+                main_function,
+                GGenericsAssignment::default(),
+                &p,
+                config,
+                &mut warnings,
+                &mut unrolled_iterations,
+            )?;
+
+            let duplicates = find_duplicated_public_inputs(&main_function);
+
+            let main_function = match config.merge_public_inputs {
+                true => {
+                    let (main_function, merged) = merge_public_inputs(main_function, duplicates);
+                    warnings.extend(merged.into_iter().map(|(kept, removed)| {
+                        Warning::MergedPublicInput {
+                            kept: kept.to_string(),
+                            removed: removed.to_string(),
+                        }
+                    }));
+                    main_function
+                }
+                false => {
+                    warnings.extend(duplicates.into_iter().map(|(first, second)| {
+                        Warning::DuplicatedPublicInput {
+                            first: first.to_string(),
+                            second: second.to_string(),
+                        }
+                    }));
+                    main_function
+                }
+            };
 
-            Ok(TypedProgram {
-                main: p.main.clone(),
-                modules: vec![(
-                    p.main.clone(),
-                    TypedModule {
-                        functions: vec![(
-                            main_key.clone(),
-                            TypedFunctionSymbol::Here(main_function),
-                        )]
+            // merging public inputs changes the signature of `main`
+            let reduced_main_key = main_key.clone().signature(main_function.signature.clone());
+
+            let (inlined_calls, max_call_depth) = count_calls(&main_function.statements);
+
+            let statement_count = main_function.statements.len();
+
+            let mut modules: TypedModules<T> = vec![(
+                p.main.clone(),
+                TypedModule {
+                    functions: vec![(reduced_main_key, TypedFunctionSymbol::Here(main_function))]
                         .into_iter()
                         .collect(),
-                        constants: Default::default(),
-                    },
-                )]
-                .into_iter()
-                .collect(),
-            })
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect();
+
+            // the calls to non-generic functions were kept, so these functions are reduced and kept as well
+            if config.inline_policy == InlinePolicy::NeverForNonGeneric {
+                for (module_id, module) in &p.modules {
+                    for (key, symbol) in &module.functions {
+                        if key == main_key || !key.signature.generics.is_empty() {
+                            continue;
+                        }
+
+                        let symbol = reduce_symbol(
+                            symbol,
+                            &p,
+                            config,
+                            &mut warnings,
+                            &mut unrolled_iterations,
+                        )?;
+
+                        insert_symbol(&mut modules, module_id, key, symbol);
+                    }
+                }
+            }
+
+            // modules and functions are not ordered, sort for the output to be deterministic
+            // nested loops can report the same issue many times, so we also remove duplicates
+            warnings.sort();
+            warnings.dedup();
+
+            let stats = ReductionStats {
+                statement_count,
+                inlined_calls,
+                max_call_depth,
+                unrolled_iterations,
+            };
+
+            Ok((
+                TypedProgram {
+                    main: p.main.clone(),
+                    modules,
+                },
+                stats,
+                warnings,
+            ))
         }
         _ => Err(Error::GenericsInMain),
     }
 }
 
-fn reduce_function<'ast, T: Field>(
+// Reduce a program, keeping the functions which can be reduced on their own if the reduction of the program fails
+// This gives tools such as editors a best-effort program to work with
+pub fn reduce_program_partial<'ast, T: Field>(
+    p: TypedProgram<'ast, T>,
+    config: &ReducerConfig,
+) -> ReduceOutcome<'ast, T> {
+    match reduce_program(p.clone(), config) {
+        Ok(program) => ReduceOutcome {
+            program,
+            error: None,
+        },
+        Err(e) => {
+            let mut modules = TypedModules::new();
+
+            for (module_id, module) in &p.modules {
+                for (key, symbol) in &module.functions {
+                    if !key.signature.generics.is_empty() {
+                        continue;
+                    }
+
+                    if let Ok(symbol) = reduce_symbol(symbol, &p, config, &mut vec![], &mut 0) {
+                        insert_symbol(&mut modules, module_id, key, symbol);
+                    }
+                }
+            }
+
+            ReduceOutcome {
+                program: TypedProgram {
+                    main: p.main,
+                    modules,
+                },
+                error: Some(e),
+            }
+        }
+    }
+}
+
+// Reduce a non-generic function symbol, leaving the symbols which are not defined in the program as is
+fn reduce_symbol<'ast, T: Field>(
+    symbol: &TypedFunctionSymbol<'ast, T>,
+    program: &TypedProgram<'ast, T>,
+    config: &ReducerConfig,
+    warnings: &mut Vec<Warning>,
+    unrolled_iterations: &mut u128,
+) -> Result<TypedFunctionSymbol<'ast, T>, Error> {
+    match symbol {
+        TypedFunctionSymbol::Here(f) => Ok(TypedFunctionSymbol::Here(reduce_specialized_function(
+            f.clone(),
+            GGenericsAssignment::default(),
+            program,
+            config,
+            warnings,
+            unrolled_iterations,
+        )?)),
+        s => Ok(s.clone()),
+    }
+}
+
+fn insert_symbol<'ast, T>(
+    modules: &mut TypedModules<'ast, T>,
+    module_id: &Path,
+    key: &DeclarationFunctionKey<'ast>,
+    symbol: TypedFunctionSymbol<'ast, T>,
+) {
+    modules
+        .entry(module_id.to_path_buf())
+        .or_insert_with(|| TypedModule {
+            functions: Default::default(),
+            constants: Default::default(),
+        })
+        .functions
+        .insert(key.clone(), symbol);
+}
+
+// Reduce a function of `program` with the given values for its generic parameters, rather than starting from `main`
+pub fn reduce_function<'ast, T: Field>(
+    f: TypedFunction<'ast, T>,
+    generics: Vec<u32>,
+    program: &TypedProgram<'ast, T>,
+    config: &ReducerConfig,
+) -> Result<TypedFunction<'ast, T>, Error> {
+    if generics.len() != f.signature.generics.len() {
+        return Err(Error::GenericArityMismatch(
+            f.signature.to_string(),
+            f.signature.generics.len(),
+            generics.len(),
+        ));
+    }
+
+    let assignment = GGenericsAssignment(
+        f.signature
+            .generics
+            .iter()
+            .zip(generics)
+            .filter_map(|(g, v)| match g {
+                Some(DeclarationConstant::Generic(g)) => Some((g.clone(), v as usize)),
+                _ => None,
+            })
+            .collect(),
+    );
+
+    reduce_specialized_function(f, assignment, program, config, &mut vec![], &mut 0)
+}
+
+fn reduce_specialized_function<'ast, T: Field>(
     f: TypedFunction<'ast, T>,
     generics: ConcreteGenericsAssignment<'ast>,
     program: &TypedProgram<'ast, T>,
+    config: &ReducerConfig,
+    warnings: &mut Vec<Warning>,
+    unrolled_iterations: &mut u128,
 ) -> Result<TypedFunction<'ast, T>, Error> {
     let mut versions = Versions::default();
 
@@ -546,12 +1191,18 @@ fn reduce_function<'ast, T: Field>(
 
             let mut hash = None;
 
+            let mut rounds = 0;
+
             loop {
+                rounds += 1;
+
                 let mut reducer = Reducer::new(
                     &program,
                     &mut versions,
                     &mut substitutions,
+                    warnings,
                     for_loop_versions,
+                    config,
                 );
 
                 let new_f = TypedFunction {
@@ -568,6 +1219,14 @@ fn reduce_function<'ast, T: Field>(
 
                 assert!(reducer.for_loop_versions.is_empty());
 
+                *unrolled_iterations += reducer.unrolled_iterations;
+
+                if let Some(max) = config.max_instantiations {
+                    if count_instantiations(&new_f.statements) > max {
+                        return Err(Error::TooManyInstantiations(max));
+                    }
+                }
+
                 match reducer.complete {
                     true => {
                         substitutions = substitutions.canonicalize();
@@ -581,6 +1240,12 @@ fn reduce_function<'ast, T: Field>(
                         break Ok(new_f);
                     }
                     false => {
+                        if let Some(max) = config.max_rounds {
+                            if rounds >= max {
+                                break Err(Error::DidNotConverge(max));
+                            }
+                        }
+
                         for_loop_versions = reducer.for_loop_versions_after;
 
                         let new_f = Sub::new(&substitutions).fold_function(new_f);
@@ -592,7 +1257,10 @@ fn reduce_function<'ast, T: Field>(
                         let new_hash = Some(compute_hash(&f));
 
                         if new_hash == hash {
-                            break Err(Error::NoProgress);
+                            break Err(match find_unresolved_size(&f) {
+                                Some(size) => Error::UnresolvableSize(size.to_string()),
+                                None => Error::NoProgress,
+                            });
                         } else {
                             hash = new_hash
                         }
@@ -603,60 +1271,289 @@ fn reduce_function<'ast, T: Field>(
     }
 }
 
-fn compute_hash<T: Field>(f: &TypedFunction<T>) -> u64 {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    let mut s = DefaultHasher::new();
-    f.hash(&mut s);
-    s.finish()
+// Find a size which is not constant in a function the reduction makes no progress on, be it the size of an array
+// type or a generic parameter of a call
+fn find_unresolved_size<'ast, T: Field>(
+    f: &TypedFunction<'ast, T>,
+) -> Option<UExpression<'ast, T>> {
+    let mut finder = UnresolvedSizeFinder::default();
+    finder.fold_function(f.clone());
+    finder.size
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::typed_absy::types::DeclarationConstant;
-    use crate::typed_absy::types::DeclarationSignature;
-    use crate::typed_absy::{
-        ArrayExpression, ArrayExpressionInner, DeclarationFunctionKey, DeclarationType,
-        DeclarationVariable, FieldElementExpression, GenericIdentifier, Identifier,
-        OwnedTypedModuleId, Select, Type, TypedExpression, TypedExpressionList,
-        TypedExpressionOrSpread, Types, UBitwidth, UExpressionInner, Variable,
-    };
-    use zokrates_field::Bn128Field;
+struct UnresolvedSizeFinder<'ast, T> {
+    size: Option<UExpression<'ast, T>>,
+}
 
-    use lazy_static::lazy_static;
+impl<'ast, T> Default for UnresolvedSizeFinder<'ast, T> {
+    fn default() -> Self {
+        UnresolvedSizeFinder { size: None }
+    }
+}
 
-    lazy_static! {
-        static ref MAIN_MODULE_ID: OwnedTypedModuleId = OwnedTypedModuleId::from("main");
+impl<'ast, T: Field> UnresolvedSizeFinder<'ast, T> {
+    fn visit(&mut self, size: &UExpression<'ast, T>) {
+        if self.size.is_none() && !matches!(size.as_inner(), UExpressionInner::Value(..)) {
+            self.size = Some(size.clone());
+        }
     }
+}
 
-    #[test]
-    fn no_generics() {
-        // def foo(field a) -> field:
-        //      return a
-        // def main(field a) -> field:
-        //      u32 n = 42
-        //      n = n
-        //      a = a
-        //      a = foo(a)
-        //      n = n
-        //      return a
+impl<'ast, T: Field> Folder<'ast, T> for UnresolvedSizeFinder<'ast, T> {
+    fn fold_array_type(&mut self, t: ArrayType<'ast, T>) -> ArrayType<'ast, T> {
+        self.visit(&t.size);
+        ArrayType {
+            ty: box self.fold_type(*t.ty),
+            size: t.size,
+        }
+    }
 
-        // expected:
-        // def main(field a_0) -> field:
-        //      a_1 = a_0
-        //      # PUSH CALL to foo
-        //          a_3 := a_1 // input binding
-        //          #RETURN_AT_INDEX_0_0 := a_3
-        //      # POP CALL
-        //      a_2 = #RETURN_AT_INDEX_0_0
-        //      return a_2
+    fn fold_function_call_expression<
+        E: Id<'ast, T> + From<TypedExpression<'ast, T>> + Expr<'ast, T> + FunctionCall<'ast, T>,
+    >(
+        &mut self,
+        ty: &E::Ty,
+        e: FunctionCallExpression<'ast, T, E>,
+    ) -> FunctionCallOrExpression<'ast, T, E> {
+        for g in e.generics.iter().flatten() {
+            self.visit(g);
+        }
+        folder::fold_function_call_expression(self, ty, e)
+    }
+}
 
-        let foo: TypedFunction<Bn128Field> = TypedFunction {
-            arguments: vec![DeclarationVariable::field_element("a").into()],
-            statements: vec![TypedStatement::Return(vec![
-                FieldElementExpression::Identifier("a".into()).into(),
-            ])],
+// Find the pairs of public inputs of a reduced `main` function which are asserted to be equal
+fn find_duplicated_public_inputs<'ast, T: Field>(
+    main: &TypedFunction<'ast, T>,
+) -> Vec<(Identifier<'ast>, Identifier<'ast>)> {
+    let public: HashSet<_> = main
+        .arguments
+        .iter()
+        .filter(|p| !p.private)
+        .map(|p| &p.id.id)
+        .collect();
+
+    main.statements
+        .iter()
+        .filter_map(|s| match s {
+            TypedStatement::Assertion(e) => identifier_eq(e),
+            _ => None,
+        })
+        .filter(|(first, second)| {
+            first != second && public.contains(first) && public.contains(second)
+        })
+        .map(|(first, second)| (first.clone(), second.clone()))
+        .collect()
+}
+
+// The operands of an equality between two identifiers, if `e` is one
+fn identifier_eq<'a, 'ast, T>(
+    e: &'a BooleanExpression<'ast, T>,
+) -> Option<(&'a Identifier<'ast>, &'a Identifier<'ast>)> {
+    match e {
+        BooleanExpression::FieldEq(
+            box FieldElementExpression::Identifier(first),
+            box FieldElementExpression::Identifier(second),
+        ) => Some((first, second)),
+        BooleanExpression::BoolEq(
+            box BooleanExpression::Identifier(first),
+            box BooleanExpression::Identifier(second),
+        ) => Some((first, second)),
+        BooleanExpression::UintEq(box first, box second) => {
+            match (first.as_inner(), second.as_inner()) {
+                (UExpressionInner::Identifier(first), UExpressionInner::Identifier(second)) => {
+                    Some((first, second))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// Merge the second public input of each pair into the first one: it is removed from the arguments and the signature
+// of `main`, its reads are replaced by reads of the input it is merged into, and the assertion that they are equal
+// is removed. Return the merged function along with the pairs of kept and removed inputs
+fn merge_public_inputs<'ast, T: Field>(
+    main: TypedFunction<'ast, T>,
+    duplicates: Vec<(Identifier<'ast>, Identifier<'ast>)>,
+) -> (
+    TypedFunction<'ast, T>,
+    Vec<(Identifier<'ast>, Identifier<'ast>)>,
+) {
+    let mut renamer = PublicInputRenamer::default();
+    let mut merged = vec![];
+
+    for (first, second) in duplicates {
+        // inputs may be asserted to be equal transitively, in which case they are already merged
+        let (kept, removed) = (renamer.resolve(&first), renamer.resolve(&second));
+
+        if kept != removed {
+            renamer.merged.insert(removed.clone(), kept.clone());
+            merged.push((kept, removed));
+        }
+    }
+
+    let (arguments, inputs) = main
+        .arguments
+        .into_iter()
+        .zip(main.signature.inputs)
+        .filter(|(p, _)| !renamer.merged.contains_key(&p.id.id))
+        .unzip();
+
+    let statements = main
+        .statements
+        .into_iter()
+        .flat_map(|s| renamer.fold_statement(s))
+        .filter(|s| match s {
+            TypedStatement::Assertion(e) => {
+                !matches!(identifier_eq(e), Some((first, second)) if first == second)
+            }
+            _ => true,
+        })
+        .collect();
+
+    (
+        TypedFunction {
+            arguments,
+            statements,
+            signature: DeclarationSignature {
+                inputs,
+                ..main.signature
+            },
+        },
+        merged,
+    )
+}
+
+// Replace the reads of merged public inputs by reads of the input they are merged into
+#[derive(Default)]
+struct PublicInputRenamer<'ast> {
+    merged: HashMap<Identifier<'ast>, Identifier<'ast>>,
+}
+
+impl<'ast> PublicInputRenamer<'ast> {
+    fn resolve(&self, id: &Identifier<'ast>) -> Identifier<'ast> {
+        match self.merged.get(id) {
+            Some(kept) => self.resolve(kept),
+            None => id.clone(),
+        }
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for PublicInputRenamer<'ast> {
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        self.resolve(&n)
+    }
+}
+
+// Propagate the constants of a single loop iteration, in which the loop index is known, and
+// return the message of the first assertion which is statically false, if any
+fn find_unsatisfiable_assertion<T: Field>(statements: &[TypedStatement<T>]) -> Option<String> {
+    let mut constants = HashMap::new();
+    let mut propagator = Propagator::with_constants(&mut constants);
+
+    for s in statements {
+        match propagator.fold_statement(s.clone()) {
+            Ok(_) => {}
+            Err(propagation::Error::AssertionFailed(message)) => return Some(message),
+            // other errors are reported when propagating the whole function
+            Err(_) => return None,
+        }
+    }
+
+    None
+}
+
+// Count the distinct instantiations of generic functions inlined in a list of statements
+fn count_instantiations<T: Field>(statements: &[TypedStatement<T>]) -> usize {
+    statements
+        .iter()
+        .filter_map(|s| match s {
+            TypedStatement::PushCallLog(key, generics) if !generics.0.is_empty() => {
+                Some((key, generics))
+            }
+            _ => None,
+        })
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+// the number of inlined calls in a reduced function, and the maximum number of nested ones
+fn count_calls<T: Field>(statements: &[TypedStatement<T>]) -> (usize, usize) {
+    let mut count = 0;
+    let mut depth = 0;
+    let mut max_depth = 0;
+
+    for s in statements {
+        match s {
+            TypedStatement::PushCallLog(..) => {
+                count += 1;
+                depth += 1;
+                max_depth = std::cmp::max(depth, max_depth);
+            }
+            TypedStatement::PopCallLog => depth -= 1,
+            _ => {}
+        }
+    }
+
+    (count, max_depth)
+}
+
+fn compute_hash<T: Field>(f: &TypedFunction<T>) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let mut s = DefaultHasher::new();
+    f.hash(&mut s);
+    s.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embed::FlatEmbed;
+    use crate::typed_absy::types::DeclarationSignature;
+    use crate::typed_absy::{
+        ArrayExpression, ArrayExpressionInner, BooleanExpression, DeclarationFunctionKey,
+        DeclarationParameter, DeclarationType, DeclarationVariable, FieldElementExpression,
+        GenericIdentifier, Identifier, OwnedTypedModuleId, Select, Type, TypedExpression,
+        TypedExpressionList, TypedExpressionOrSpread, Types, UBitwidth, UExpressionInner, Variable,
+    };
+    use zokrates_field::Bn128Field;
+
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref MAIN_MODULE_ID: OwnedTypedModuleId = OwnedTypedModuleId::from("main");
+    }
+
+    #[test]
+    fn no_generics() {
+        // def foo(field a) -> field:
+        //      return a
+        // def main(field a) -> field:
+        //      u32 n = 42
+        //      n = n
+        //      a = a
+        //      a = foo(a)
+        //      n = n
+        //      return a
+
+        // expected:
+        // def main(field a_0) -> field:
+        //      a_1 = a_0
+        //      # PUSH CALL to foo
+        //          a_3 := a_1 // input binding
+        //          #RETURN_AT_INDEX_0_0 := a_3
+        //      # POP CALL
+        //      a_2 = #RETURN_AT_INDEX_0_0
+        //      return a_2
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![
+                FieldElementExpression::Identifier("a".into()).into(),
+            ])],
             signature: DeclarationSignature::new()
                 .inputs(vec![DeclarationType::FieldElement])
                 .outputs(vec![DeclarationType::FieldElement]),
@@ -737,7 +1634,7 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        let reduced = reduce_program_with_report(p, &ReducerConfig::default());
 
         let expected_main = TypedFunction {
             arguments: vec![DeclarationVariable::field_element("a").into()],
@@ -781,6 +1678,8 @@ mod tests {
                 .outputs(vec![DeclarationType::FieldElement]),
         };
 
+        let expected_statement_count = expected_main.statements.len();
+
         let expected: TypedProgram<Bn128Field> = TypedProgram {
             main: "main".into(),
             modules: vec![(
@@ -803,7 +1702,10 @@ mod tests {
             .collect(),
         };
 
-        assert_eq!(reduced.unwrap(), expected);
+        let (reduced, stats, _) = reduced.unwrap();
+
+        assert_eq!(reduced, expected);
+        assert_eq!(stats.statement_count, expected_statement_count);
     }
 
     #[test]
@@ -937,7 +1839,7 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        let reduced = reduce_program(p, &ReducerConfig::default());
 
         let expected_main = TypedFunction {
             arguments: vec![DeclarationVariable::field_element("a").into()],
@@ -1165,7 +2067,7 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        let reduced = reduce_program(p, &ReducerConfig::default());
 
         let expected_main = TypedFunction {
             arguments: vec![DeclarationVariable::field_element("a").into()],
@@ -1254,149 +2156,2436 @@ mod tests {
     }
 
     #[test]
-    fn call_in_call() {
-        // we use a global ssa counter, hence reusing variable names in called functions
-        // leads to counter increase
-
-        // def bar<K>(field[K] a) -> field[K]:
+    fn for_loop() {
+        // def main(field a) -> field:
+        //      for u32 i in 0..2 do
+        //          a = a * a
+        //      endfor
         //      return a
 
-        // def foo<K>(field[K] a) -> field[K]:
-        //      field[K] ret = bar([...a, 0])[0..K]
-        //      return ret
-
-        // def main():
-        //      field[1] b = foo([1])
-        //      return
-
         // expected:
-        // def main():
-        //      # PUSH CALL to foo::<1>
-        //          # PUSH CALL to bar::<2>
-        //              field[2] a_1 = [...[1]], 0]
-        //              field[2] #RET_0_1 = a_1
-        //          # POP CALL
-        //          field[1] ret := #RET_0_1[0..1]
-        //          field[1] #RET_0 = ret
-        //      # POP CALL
-        //      field[1] b_0 := #RET_0
-        //      return
+        // def main(field a_0) -> field:
+        //      a_4 = a_0 * a_0
+        //      a_5 = a_4 * a_4
+        //      return a_5
 
-        let foo_signature = DeclarationSignature::new()
-            .inputs(vec![DeclarationType::array((
-                DeclarationType::FieldElement,
-                DeclarationConstant::Generic(GenericIdentifier::with_name("K").index(0)),
-            ))])
-            .outputs(vec![DeclarationType::array((
-                DeclarationType::FieldElement,
-                DeclarationConstant::Generic(GenericIdentifier::with_name("K").index(0)),
-            ))])
-            .generics(vec![Some(
-                GenericIdentifier::with_name("K").index(0).into(),
-            )]);
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
 
-        let foo: TypedFunction<Bn128Field> = TypedFunction {
-            arguments: vec![DeclarationVariable::array(
-                "a",
-                DeclarationType::FieldElement,
-                DeclarationConstant::Generic(GenericIdentifier::with_name("K").index(0)),
-            )
-            .into()],
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
             statements: vec![
-                TypedStatement::Definition(
-                    Variable::array(
-                        "ret",
-                        Type::FieldElement,
-                        UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
-                    )
-                    .into(),
-                    ArrayExpressionInner::Slice(
-                        box ArrayExpression::function_call(
-                            DeclarationFunctionKey::with_location("main", "bar")
-                                .signature(foo_signature.clone()),
-                            vec![None],
-                            vec![ArrayExpressionInner::Value(
-                                vec![
-                                    TypedExpressionOrSpread::Spread(
-                                        ArrayExpressionInner::Identifier("a".into())
-                                            .annotate(Type::FieldElement, 1u32)
-                                            .into(),
-                                    ),
-                                    FieldElementExpression::Number(Bn128Field::from(0)).into(),
-                                ]
-                                .into(),
-                            )
-                            .annotate(
-                                Type::FieldElement,
-                                UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32)
-                                    + 1u32.into(),
-                            )
-                            .into()],
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    2u32.into(),
+                    vec![TypedStatement::Definition(
+                        Variable::field_element("a").into(),
+                        FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("a".into()),
+                            box FieldElementExpression::Identifier("a".into()),
                         )
-                        .annotate(
-                            Type::FieldElement,
-                            UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32)
-                                + 1u32.into(),
-                        ),
-                        box 0u32.into(),
-                        box UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
-                    )
-                    .annotate(
-                        Type::FieldElement,
-                        UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
-                    )
-                    .into(),
+                        .into(),
+                    )],
                 ),
-                TypedStatement::Return(vec![ArrayExpressionInner::Identifier("ret".into())
-                    .annotate(
-                        Type::FieldElement,
-                        UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
-                    )
-                    .into()]),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
             ],
-            signature: foo_signature.clone(),
+            signature: main_signature.clone(),
         };
 
-        let bar_signature = foo_signature.clone();
-
-        let bar: TypedFunction<Bn128Field> = TypedFunction {
-            arguments: vec![DeclarationVariable::array(
-                "a",
-                DeclarationType::FieldElement,
-                DeclarationConstant::Generic(GenericIdentifier::with_name("K").index(0)),
-            )
-            .into()],
-            statements: vec![TypedStatement::Return(vec![
-                ArrayExpressionInner::Identifier("a".into())
-                    .annotate(
-                        Type::FieldElement,
-                        UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
-                    )
-                    .into(),
-            ])],
-            signature: bar_signature.clone(),
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature.clone()),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
         };
 
-        let main: TypedFunction<Bn128Field> = TypedFunction {
-            arguments: vec![],
+        let a =
+            |version| FieldElementExpression::Identifier(Identifier::from("a").version(version));
+
+        let expected_main = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
             statements: vec![
-                TypedStatement::MultipleDefinition(
-                    vec![Variable::array("b", Type::FieldElement, 1u32).into()],
-                    TypedExpressionList::function_call(
-                        DeclarationFunctionKey::with_location("main", "foo")
-                            .signature(foo_signature.clone()),
-                        vec![None],
-                        vec![ArrayExpressionInner::Value(
-                            vec![FieldElementExpression::Number(Bn128Field::from(1)).into()].into(),
-                        )
-                        .annotate(Type::FieldElement, 1u32)
-                        .into()],
-                    )
-                    .annotate(Types::new(vec![Type::array((Type::FieldElement, 1u32))])),
+                TypedStatement::Definition(
+                    Variable::field_element(Identifier::from("a").version(4)).into(),
+                    FieldElementExpression::Mult(box a(0), box a(0)).into(),
                 ),
-                TypedStatement::Return(vec![]),
+                TypedStatement::Definition(
+                    Variable::field_element(Identifier::from("a").version(5)).into(),
+                    FieldElementExpression::Mult(box a(4), box a(4)).into(),
+                ),
+                TypedStatement::Return(vec![a(5).into()]),
             ],
-            signature: DeclarationSignature::new(),
+            signature: main_signature.clone(),
+        };
+
+        let expected: TypedProgram<Bn128Field> = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature),
+                        TypedFunctionSymbol::Here(expected_main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced = reduce_program(p, &ReducerConfig::default());
+
+        assert_eq!(reduced.unwrap(), expected);
+    }
+
+    #[test]
+    fn nested_for_loop() {
+        // def main(field[2] a) -> field[2]:
+        //      for u32 i in 0..2 do
+        //          for u32 j in 0..2 do
+        //              a[i] = a[i] * a[j]
+        //          endfor
+        //      endfor
+        //      return a
+
+        // expected:
+        // def main(field[2] a) -> field[2]:
+        //      a[0] = a[0] * a[0]
+        //      a[0] = a[0] * a[1]
+        //      a[1] = a[1] * a[0]
+        //      a[1] = a[1] * a[1]
+        //      return a
+
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                2u32,
+            ))])
+            .outputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                2u32,
+            ))]);
+
+        let a = || ArrayExpressionInner::Identifier("a".into()).annotate(Type::FieldElement, 2u32);
+
+        let update = |i: UExpression<'static, Bn128Field>, j: UExpression<'static, Bn128Field>| {
+            TypedStatement::Definition(
+                TypedAssignee::Select(
+                    box Variable::array("a", Type::FieldElement, 2u32).into(),
+                    box i.clone(),
+                ),
+                FieldElementExpression::Mult(
+                    box FieldElementExpression::select(a(), i),
+                    box FieldElementExpression::select(a(), j),
+                )
+                .into(),
+            )
+        };
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![
+                DeclarationVariable::array("a", DeclarationType::FieldElement, 2u32).into(),
+            ],
+            statements: vec![
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    2u32.into(),
+                    vec![TypedStatement::For(
+                        Variable::uint("j", UBitwidth::B32),
+                        0u32.into(),
+                        2u32.into(),
+                        vec![update(
+                            UExpressionInner::Identifier("i".into()).annotate(UBitwidth::B32),
+                            UExpressionInner::Identifier("j".into()).annotate(UBitwidth::B32),
+                        )],
+                    )],
+                ),
+                TypedStatement::Return(vec![a().into()]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let program = |main| TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature.clone()),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let expected_main = TypedFunction {
+            arguments: vec![
+                DeclarationVariable::array("a", DeclarationType::FieldElement, 2u32).into(),
+            ],
+            statements: vec![
+                update(0u32.into(), 0u32.into()),
+                update(0u32.into(), 1u32.into()),
+                update(1u32.into(), 0u32.into()),
+                update(1u32.into(), 1u32.into()),
+                TypedStatement::Return(vec![a().into()]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let (reduced, stats, _) =
+            reduce_program_with_report(program(main), &ReducerConfig::default()).unwrap();
+
+        // the writes to the array do not create new versions, so all uses of `a` refer to the same version
+        assert!(reduced.alpha_eq(&program(expected_main)));
+        // two iterations of the outer loop, then two of each inner loop
+        assert_eq!(stats.unrolled_iterations, 6);
+    }
+
+    #[test]
+    fn for_loop_with_computed_bound() {
+        // def main(field a) -> field:
+        //      u32 n = 1
+        //      u32 m = n + 1
+        //      u32 k = m * 2
+        //      for u32 i in 0..k do
+        //          a = a * a
+        //      endfor
+        //      return a
+
+        // expected:
+        // def main(field a_0) -> field:
+        //      a_4 = a_0 * a_0
+        //      a_5 = a_4 * a_4
+        //      a_6 = a_5 * a_5
+        //      a_7 = a_6 * a_6
+        //      return a_7
+
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let u32_identifier =
+            |name: &'static str| UExpressionInner::Identifier(name.into()).annotate(UBitwidth::B32);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![
+                TypedStatement::Definition(
+                    Variable::uint("n", UBitwidth::B32).into(),
+                    UExpression::from(1u32).into(),
+                ),
+                TypedStatement::Definition(
+                    Variable::uint("m", UBitwidth::B32).into(),
+                    UExpressionInner::Add(box u32_identifier("n"), box 1u32.into())
+                        .annotate(UBitwidth::B32)
+                        .into(),
+                ),
+                TypedStatement::Definition(
+                    Variable::uint("k", UBitwidth::B32).into(),
+                    UExpressionInner::Mult(box u32_identifier("m"), box 2u32.into())
+                        .annotate(UBitwidth::B32)
+                        .into(),
+                ),
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    u32_identifier("k"),
+                    vec![TypedStatement::Definition(
+                        Variable::field_element("a").into(),
+                        FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("a".into()),
+                            box FieldElementExpression::Identifier("a".into()),
+                        )
+                        .into(),
+                    )],
+                ),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature.clone()),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let a =
+            |version| FieldElementExpression::Identifier(Identifier::from("a").version(version));
+
+        let expected_main = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![(0, 4), (4, 5), (5, 6), (6, 7)]
+                .into_iter()
+                .map(|(from, to)| {
+                    TypedStatement::Definition(
+                        Variable::field_element(Identifier::from("a").version(to)).into(),
+                        FieldElementExpression::Mult(box a(from), box a(from)).into(),
+                    )
+                })
+                .chain(std::iter::once(TypedStatement::Return(vec![a(7).into()])))
+                .collect(),
+            signature: main_signature.clone(),
+        };
+
+        let expected: TypedProgram<Bn128Field> = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature),
+                        TypedFunctionSymbol::Here(expected_main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced = reduce_program(p, &ReducerConfig::default());
+
+        assert_eq!(reduced.unwrap(), expected);
+    }
+
+    #[test]
+    fn call_in_call() {
+        // we use a global ssa counter, hence reusing variable names in called functions
+        // leads to counter increase
+
+        // def bar<K>(field[K] a) -> field[K]:
+        //      return a
+
+        // def foo<K>(field[K] a) -> field[K]:
+        //      field[K] ret = bar([...a, 0])[0..K]
+        //      return ret
+
+        // def main():
+        //      field[1] b = foo([1])
+        //      return
+
+        // expected:
+        // def main():
+        //      # PUSH CALL to foo::<1>
+        //          # PUSH CALL to bar::<2>
+        //              field[2] a_1 = [...[1]], 0]
+        //              field[2] #RET_0_1 = a_1
+        //          # POP CALL
+        //          field[1] ret := #RET_0_1[0..1]
+        //          field[1] #RET_0 = ret
+        //      # POP CALL
+        //      field[1] b_0 := #RET_0
+        //      return
+
+        let foo_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                DeclarationConstant::Generic(GenericIdentifier::with_name("K").index(0)),
+            ))])
+            .outputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                DeclarationConstant::Generic(GenericIdentifier::with_name("K").index(0)),
+            ))])
+            .generics(vec![Some(
+                GenericIdentifier::with_name("K").index(0).into(),
+            )]);
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::array(
+                "a",
+                DeclarationType::FieldElement,
+                DeclarationConstant::Generic(GenericIdentifier::with_name("K").index(0)),
+            )
+            .into()],
+            statements: vec![
+                TypedStatement::Definition(
+                    Variable::array(
+                        "ret",
+                        Type::FieldElement,
+                        UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
+                    )
+                    .into(),
+                    ArrayExpressionInner::Slice(
+                        box ArrayExpression::function_call(
+                            DeclarationFunctionKey::with_location("main", "bar")
+                                .signature(foo_signature.clone()),
+                            vec![None],
+                            vec![ArrayExpressionInner::Value(
+                                vec![
+                                    TypedExpressionOrSpread::Spread(
+                                        ArrayExpressionInner::Identifier("a".into())
+                                            .annotate(Type::FieldElement, 1u32)
+                                            .into(),
+                                    ),
+                                    FieldElementExpression::Number(Bn128Field::from(0)).into(),
+                                ]
+                                .into(),
+                            )
+                            .annotate(
+                                Type::FieldElement,
+                                UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32)
+                                    + 1u32.into(),
+                            )
+                            .into()],
+                        )
+                        .annotate(
+                            Type::FieldElement,
+                            UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32)
+                                + 1u32.into(),
+                        ),
+                        box 0u32.into(),
+                        box UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
+                    )
+                    .annotate(
+                        Type::FieldElement,
+                        UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
+                    )
+                    .into(),
+                ),
+                TypedStatement::Return(vec![ArrayExpressionInner::Identifier("ret".into())
+                    .annotate(
+                        Type::FieldElement,
+                        UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
+                    )
+                    .into()]),
+            ],
+            signature: foo_signature.clone(),
+        };
+
+        let bar_signature = foo_signature.clone();
+
+        let bar: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::array(
+                "a",
+                DeclarationType::FieldElement,
+                DeclarationConstant::Generic(GenericIdentifier::with_name("K").index(0)),
+            )
+            .into()],
+            statements: vec![TypedStatement::Return(vec![
+                ArrayExpressionInner::Identifier("a".into())
+                    .annotate(
+                        Type::FieldElement,
+                        UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
+                    )
+                    .into(),
+            ])],
+            signature: bar_signature.clone(),
+        };
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::MultipleDefinition(
+                    vec![Variable::array("b", Type::FieldElement, 1u32).into()],
+                    TypedExpressionList::function_call(
+                        DeclarationFunctionKey::with_location("main", "foo")
+                            .signature(foo_signature.clone()),
+                        vec![None],
+                        vec![ArrayExpressionInner::Value(
+                            vec![FieldElementExpression::Number(Bn128Field::from(1)).into()].into(),
+                        )
+                        .annotate(Type::FieldElement, 1u32)
+                        .into()],
+                    )
+                    .annotate(Types::new(vec![Type::array((Type::FieldElement, 1u32))])),
+                ),
+                TypedStatement::Return(vec![]),
+            ],
+            signature: DeclarationSignature::new(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (
+                            DeclarationFunctionKey::with_location("main", "bar")
+                                .signature(bar_signature.clone()),
+                            TypedFunctionSymbol::Here(bar),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(foo_signature.clone()),
+                            TypedFunctionSymbol::Here(foo),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "main"),
+                            TypedFunctionSymbol::Here(main),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced = reduce_program(p, &ReducerConfig::default());
+
+        let expected_main = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::PushCallLog(
+                    DeclarationFunctionKey::with_location("main", "foo")
+                        .signature(foo_signature.clone()),
+                    GGenericsAssignment(
+                        vec![(GenericIdentifier::with_name("K").index(0), 1)]
+                            .into_iter()
+                            .collect(),
+                    ),
+                ),
+                TypedStatement::PushCallLog(
+                    DeclarationFunctionKey::with_location("main", "bar")
+                        .signature(foo_signature.clone()),
+                    GGenericsAssignment(
+                        vec![(GenericIdentifier::with_name("K").index(0), 2)]
+                            .into_iter()
+                            .collect(),
+                    ),
+                ),
+                TypedStatement::Definition(
+                    Variable::array(Identifier::from("a").version(1), Type::FieldElement, 2u32)
+                        .into(),
+                    ArrayExpressionInner::Value(
+                        vec![
+                            TypedExpressionOrSpread::Spread(
+                                ArrayExpressionInner::Value(
+                                    vec![TypedExpressionOrSpread::Expression(
+                                        FieldElementExpression::Number(Bn128Field::from(1)).into(),
+                                    )]
+                                    .into(),
+                                )
+                                .annotate(Type::FieldElement, 1u32)
+                                .into(),
+                            ),
+                            FieldElementExpression::Number(Bn128Field::from(0)).into(),
+                        ]
+                        .into(),
+                    )
+                    .annotate(Type::FieldElement, 2u32)
+                    .into(),
+                ),
+                TypedStatement::Definition(
+                    Variable::array(
+                        Identifier::from(CoreIdentifier::Call(0)).version(1),
+                        Type::FieldElement,
+                        2u32,
+                    )
+                    .into(),
+                    ArrayExpressionInner::Identifier(Identifier::from("a").version(1))
+                        .annotate(Type::FieldElement, 2u32)
+                        .into(),
+                ),
+                TypedStatement::PopCallLog,
+                TypedStatement::Definition(
+                    Variable::array("ret", Type::FieldElement, 1u32).into(),
+                    ArrayExpressionInner::Slice(
+                        box ArrayExpressionInner::Identifier(
+                            Identifier::from(CoreIdentifier::Call(0)).version(1),
+                        )
+                        .annotate(Type::FieldElement, 2u32),
+                        box 0u32.into(),
+                        box 1u32.into(),
+                    )
+                    .annotate(Type::FieldElement, 1u32)
+                    .into(),
+                ),
+                TypedStatement::Definition(
+                    Variable::array(
+                        Identifier::from(CoreIdentifier::Call(0)),
+                        Type::FieldElement,
+                        1u32,
+                    )
+                    .into(),
+                    ArrayExpressionInner::Identifier("ret".into())
+                        .annotate(Type::FieldElement, 1u32)
+                        .into(),
+                ),
+                TypedStatement::PopCallLog,
+                TypedStatement::Definition(
+                    Variable::array("b", Type::FieldElement, 1u32).into(),
+                    ArrayExpressionInner::Identifier(Identifier::from(CoreIdentifier::Call(0)))
+                        .annotate(Type::FieldElement, 1u32)
+                        .into(),
+                ),
+                TypedStatement::Return(vec![]),
+            ],
+            signature: DeclarationSignature::new(),
+        };
+
+        let expected = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(DeclarationSignature::new()),
+                        TypedFunctionSymbol::Here(expected_main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        assert_eq!(reduced.unwrap(), expected);
+    }
+
+    #[test]
+    fn incompatible() {
+        // def foo<K>(field[K] a) -> field[K]:
+        //      return a
+        // def main():
+        //      field[1] b = foo([])
+        //      return
+
+        // expected:
+        // Error: Incompatible
+
+        let foo_signature = DeclarationSignature::new()
+            .generics(vec![Some(
+                GenericIdentifier::with_name("K").index(0).into(),
+            )])
+            .inputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("K").index(0),
+            ))])
+            .outputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("K").index(0),
+            ))]);
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::array(
+                "a",
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("K").index(0),
+            )
+            .into()],
+            statements: vec![TypedStatement::Return(vec![
+                ArrayExpressionInner::Identifier("a".into())
+                    .annotate(Type::FieldElement, 1u32)
+                    .into(),
+            ])],
+            signature: foo_signature.clone(),
+        };
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::MultipleDefinition(
+                    vec![Variable::array("b", Type::FieldElement, 1u32).into()],
+                    TypedExpressionList::function_call(
+                        DeclarationFunctionKey::with_location("main", "foo")
+                            .signature(foo_signature.clone()),
+                        vec![None],
+                        vec![ArrayExpressionInner::Value(vec![].into())
+                            .annotate(Type::FieldElement, 0u32)
+                            .into()],
+                    )
+                    .annotate(Types::new(vec![Type::array((Type::FieldElement, 1u32))])),
+                ),
+                TypedStatement::Return(vec![]),
+            ],
+            signature: DeclarationSignature::new().inputs(vec![]).outputs(vec![]),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(foo_signature.clone()),
+                            TypedFunctionSymbol::Here(foo),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "main").signature(
+                                DeclarationSignature::new().inputs(vec![]).outputs(vec![]),
+                            ),
+                            TypedFunctionSymbol::Here(main),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced = reduce_program(p, &ReducerConfig::default());
+
+        assert_eq!(
+            reduced,
+            Err(Error::Incompatible("Call site `main/foo<_>(field[0]) -> field[1]` incompatible with declaration `main/foo<K>(field[K]) -> field[K]`".into()))
+        );
+    }
+
+    #[test]
+    fn unused_generic() {
+        // def foo<N, M>(field[N] a) -> field[N]:
+        //      return a
+        // def main(field a) -> field:
+        //      return a
+
+        // expected warning: `M` is never used in `foo`
+
+        let foo_signature = DeclarationSignature::new()
+            .generics(vec![
+                Some(GenericIdentifier::with_name("N").index(0).into()),
+                Some(GenericIdentifier::with_name("M").index(1).into()),
+            ])
+            .inputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                DeclarationConstant::Generic(GenericIdentifier::with_name("N").index(0)),
+            ))])
+            .outputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                DeclarationConstant::Generic(GenericIdentifier::with_name("N").index(0)),
+            ))]);
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::array(
+                "a",
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("N").index(0),
+            )
+            .into()],
+            statements: vec![TypedStatement::Return(vec![
+                ArrayExpressionInner::Identifier("a".into())
+                    .annotate(
+                        Type::FieldElement,
+                        UExpressionInner::Identifier("N".into()).annotate(UBitwidth::B32),
+                    )
+                    .into(),
+            ])],
+            signature: foo_signature.clone(),
+        };
+
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![
+                FieldElementExpression::Identifier("a".into()).into(),
+            ])],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(foo_signature),
+                            TypedFunctionSymbol::Here(foo),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(main_signature),
+                            TypedFunctionSymbol::Here(main),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let (_, _, warnings) = reduce_program_with_report(p, &ReducerConfig::default()).unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![Warning::UnusedGeneric {
+                function: "foo".into(),
+                generic: "M".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn generic_used_in_assertion() {
+        // def foo<N>() -> field:
+        //      assert(N > 0)
+        //      return 1
+
+        // expected: no warning, as checking `N` is a legitimate use
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::Assertion(BooleanExpression::UintGt(
+                    box UExpressionInner::Identifier("N".into()).annotate(UBitwidth::B32),
+                    box 0u32.into(),
+                )),
+                TypedStatement::Return(vec![
+                    FieldElementExpression::Number(Bn128Field::from(1)).into()
+                ]),
+            ],
+            signature: DeclarationSignature::new()
+                .generics(vec![Some(
+                    GenericIdentifier::with_name("N").index(0).into(),
+                )])
+                .outputs(vec![DeclarationType::FieldElement]),
+        };
+
+        assert_eq!(UnusedGenericsDetector::detect(&foo), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn duplicated_constant_array() {
+        // def main() -> field:
+        //      for u32 i in 0..2 do
+        //          field[101] a = [0, ..., 0]
+        //      endfor
+        //      return 1
+
+        // expected warning: the array of size 101 is duplicated in 2 iterations
+
+        let main_signature =
+            DeclarationSignature::new().outputs(vec![DeclarationType::FieldElement]);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    2u32.into(),
+                    vec![TypedStatement::Definition(
+                        Variable::array("a", Type::FieldElement, 101u32).into(),
+                        ArrayExpressionInner::Value(
+                            vec![FieldElementExpression::Number(Bn128Field::from(0)).into(); 101]
+                                .into(),
+                        )
+                        .annotate(Type::FieldElement, 101u32)
+                        .into(),
+                    )],
+                ),
+                TypedStatement::Return(vec![
+                    FieldElementExpression::Number(Bn128Field::from(1)).into()
+                ]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let (_, _, warnings) = reduce_program_with_report(p, &ReducerConfig::default()).unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![Warning::DuplicatedConstantArray {
+                size: 101,
+                iterations: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn unsatisfiable_assertion_in_loop() {
+        // def main():
+        //      for u32 i in 0..5 do
+        //          assert(i < 3)
+        //      endfor
+        //      return
+
+        // expected error: the assertion fails in iteration 3
+
+        let main_signature = DeclarationSignature::new();
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    5u32.into(),
+                    vec![TypedStatement::Assertion(BooleanExpression::UintLt(
+                        box UExpressionInner::Identifier("i".into()).annotate(UBitwidth::B32),
+                        box 3u32.into(),
+                    ))],
+                ),
+                TypedStatement::Return(vec![]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        assert!(matches!(
+            reduce_program(p, &ReducerConfig::default()),
+            Err(Error::UnsatisfiableAssertion(3, _))
+        ));
+    }
+
+    #[test]
+    fn empty_loop() {
+        // def main():
+        //      for u32 i in 0..10 do
+        //      endfor
+        //      return
+
+        // expected warning: the loop over i has 10 iterations and an empty body
+
+        let main_signature = DeclarationSignature::new();
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    10u32.into(),
+                    vec![],
+                ),
+                TypedStatement::Return(vec![]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let (_, _, warnings) = reduce_program_with_report(p, &ReducerConfig::default()).unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![Warning::EmptyLoop {
+                variable: "i".into(),
+                iterations: 10
+            }]
+        );
+    }
+
+    #[test]
+    fn constant_output() {
+        // def foo(field a) -> field:
+        //      return 0
+        // def main(field a) -> field:
+        //      a = foo(a)
+        //      return a
+
+        // expected:
+        // def main(field a_0) -> field:
+        //      return 0
+
+        let foo_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![
+                FieldElementExpression::Number(Bn128Field::from(0)).into(),
+            ])],
+            signature: foo_signature.clone(),
+        };
+
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![
+                TypedStatement::MultipleDefinition(
+                    vec![Variable::field_element("a").into()],
+                    TypedExpressionList::function_call(
+                        DeclarationFunctionKey::with_location("main", "foo")
+                            .signature(foo_signature.clone()),
+                        vec![],
+                        vec![FieldElementExpression::Identifier("a".into()).into()],
+                    )
+                    .annotate(Types::new(vec![Type::FieldElement])),
+                ),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(foo_signature),
+                            TypedFunctionSymbol::Here(foo),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(main_signature.clone()),
+                            TypedFunctionSymbol::Here(main),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let expected_main = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![
+                FieldElementExpression::Number(Bn128Field::from(0)).into(),
+            ])],
+            signature: main_signature.clone(),
+        };
+
+        let expected: TypedProgram<Bn128Field> = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature),
+                        TypedFunctionSymbol::Here(expected_main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced = reduce_program(p, &ReducerConfig::default()).unwrap();
+
+        assert_eq!(reduced, expected);
+    }
+
+    #[test]
+    fn constant_output_with_effects() {
+        // def foo(field a) -> field:
+        //      return 0
+        // def main(field a) -> field:
+        //      a = foo(a / a)
+        //      return a
+
+        // the division checks that `a` is nonzero, so the call is inlined to keep it
+
+        let foo_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![
+                FieldElementExpression::Number(Bn128Field::from(0)).into(),
+            ])],
+            signature: foo_signature.clone(),
+        };
+
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![
+                TypedStatement::MultipleDefinition(
+                    vec![Variable::field_element("a").into()],
+                    TypedExpressionList::function_call(
+                        DeclarationFunctionKey::with_location("main", "foo")
+                            .signature(foo_signature.clone()),
+                        vec![],
+                        vec![FieldElementExpression::Div(
+                            box FieldElementExpression::Identifier("a".into()),
+                            box FieldElementExpression::Identifier("a".into()),
+                        )
+                        .into()],
+                    )
+                    .annotate(Types::new(vec![Type::FieldElement])),
+                ),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(foo_signature),
+                            TypedFunctionSymbol::Here(foo),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(main_signature.clone()),
+                            TypedFunctionSymbol::Here(main),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced = reduce_program(p, &ReducerConfig::default()).unwrap();
+
+        let main = match reduced.modules[&*MAIN_MODULE_ID].functions.values().next() {
+            Some(TypedFunctionSymbol::Here(main)) => main.clone(),
+            _ => unreachable!(),
+        };
+
+        assert!(main.statements.iter().any(|s| matches!(
+            s,
+            TypedStatement::Definition(
+                _,
+                TypedExpression::FieldElement(FieldElementExpression::Div(..))
+            )
+        )));
+    }
+
+    #[test]
+    fn constant_multiple_output() {
+        // def foo(field a) -> (field, field):
+        //      return a + 1, a * 2
+        // field x, field y = foo(2)
+
+        // expected:
+        // x = 3
+        // y = 4
+
+        let foo_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![
+                DeclarationType::FieldElement,
+                DeclarationType::FieldElement,
+            ]);
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![
+                FieldElementExpression::Add(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                )
+                .into(),
+                FieldElementExpression::Mult(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(2)),
+                )
+                .into(),
+            ])],
+            signature: foo_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "foo")
+                            .signature(foo_signature.clone()),
+                        TypedFunctionSymbol::Here(foo),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let call = TypedStatement::MultipleDefinition(
+            vec![
+                Variable::field_element("x").into(),
+                Variable::field_element("y").into(),
+            ],
+            TypedExpressionList::function_call(
+                DeclarationFunctionKey::with_location("main", "foo").signature(foo_signature),
+                vec![],
+                vec![FieldElementExpression::Number(Bn128Field::from(2)).into()],
+            )
+            .annotate(Types::new(vec![Type::FieldElement, Type::FieldElement])),
+        );
+
+        let mut versions = Versions::default();
+        let mut substitutions = Substitutions::default();
+        let mut warnings = vec![];
+        let config = ReducerConfig::default();
+
+        let mut reducer = Reducer::new(
+            &p,
+            &mut versions,
+            &mut substitutions,
+            &mut warnings,
+            vec![],
+            &config,
+        );
+
+        assert_eq!(
+            reducer.fold_statement(call),
+            Ok(vec![
+                TypedStatement::Definition(
+                    Variable::field_element("x").into(),
+                    FieldElementExpression::Number(Bn128Field::from(3)).into()
+                ),
+                TypedStatement::Definition(
+                    Variable::field_element("y").into(),
+                    FieldElementExpression::Number(Bn128Field::from(4)).into()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn loop_invariant_accumulation() {
+        // for u32 i in 0..5 do
+        //      t = t + x
+        // endfor
+
+        // expected:
+        // t = t + 5 * x
+
+        let index = Variable::uint("i", UBitwidth::B32);
+
+        let statements: Vec<TypedStatement<Bn128Field>> = vec![TypedStatement::Definition(
+            Variable::field_element("t").into(),
+            FieldElementExpression::Add(
+                box FieldElementExpression::Identifier("t".into()),
+                box FieldElementExpression::Identifier("x".into()),
+            )
+            .into(),
+        )];
+
+        assert_eq!(
+            accumulation(&index, &statements, 5),
+            Some(TypedStatement::Definition(
+                Variable::field_element("t").into(),
+                FieldElementExpression::Add(
+                    box FieldElementExpression::Identifier("t".into()),
+                    box FieldElementExpression::Mult(
+                        box FieldElementExpression::Number(Bn128Field::from(5)),
+                        box FieldElementExpression::Identifier("x".into()),
+                    ),
+                )
+                .into(),
+            ))
+        );
+
+        // t = t + i depends on the loop and is unrolled
+        let statements: Vec<TypedStatement<Bn128Field>> = vec![TypedStatement::Definition(
+            Variable::field_element("t").into(),
+            FieldElementExpression::Add(
+                box FieldElementExpression::Identifier("t".into()),
+                box FieldElementExpression::Identifier("i".into()),
+            )
+            .into(),
+        )];
+
+        assert_eq!(accumulation(&index, &statements, 5), None);
+    }
+
+    #[test]
+    fn unused_boolean() {
+        // def main(field a, field b) -> field:
+        //      bool c = a == b
+        //      bool d = a == 1
+        //      assert(d)
+        //      return a
+
+        // expected warning: c is never used
+
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![
+                DeclarationType::FieldElement,
+                DeclarationType::FieldElement,
+            ])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![
+                DeclarationVariable::field_element("a").into(),
+                DeclarationVariable::field_element("b").into(),
+            ],
+            statements: vec![
+                TypedStatement::Declaration(Variable::boolean("c")),
+                TypedStatement::Definition(
+                    Variable::boolean("c").into(),
+                    BooleanExpression::FieldEq(
+                        box FieldElementExpression::Identifier("a".into()),
+                        box FieldElementExpression::Identifier("b".into()),
+                    )
+                    .into(),
+                ),
+                TypedStatement::Declaration(Variable::boolean("d")),
+                TypedStatement::Definition(
+                    Variable::boolean("d").into(),
+                    BooleanExpression::FieldEq(
+                        box FieldElementExpression::Identifier("a".into()),
+                        box FieldElementExpression::Number(Bn128Field::from(1)),
+                    )
+                    .into(),
+                ),
+                TypedStatement::Assertion(BooleanExpression::Identifier("d".into())),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let (_, _, warnings) = reduce_program_with_report(p, &ReducerConfig::default()).unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![Warning::UnusedBoolean {
+                function: "main".into(),
+                variable: "c".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn duplicated_public_input() {
+        // def main(field a, field b, private field c):
+        //      assert(a == b)
+        //      assert(a == c)
+        //      return
+
+        // expected warning: the public inputs a and b are asserted to be equal
+
+        let main_signature = DeclarationSignature::new().inputs(vec![
+            DeclarationType::FieldElement,
+            DeclarationType::FieldElement,
+            DeclarationType::FieldElement,
+        ]);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![
+                DeclarationParameter {
+                    id: DeclarationVariable::field_element("a"),
+                    private: false,
+                },
+                DeclarationParameter {
+                    id: DeclarationVariable::field_element("b"),
+                    private: false,
+                },
+                DeclarationParameter {
+                    id: DeclarationVariable::field_element("c"),
+                    private: true,
+                },
+            ],
+            statements: vec![
+                TypedStatement::Assertion(BooleanExpression::FieldEq(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Identifier("b".into()),
+                )),
+                TypedStatement::Assertion(BooleanExpression::FieldEq(
+                    box FieldElementExpression::Identifier("a".into()),
+                    box FieldElementExpression::Identifier("c".into()),
+                )),
+                TypedStatement::Return(vec![]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let (_, _, warnings) = reduce_program_with_report(p, &ReducerConfig::default()).unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![Warning::DuplicatedPublicInput {
+                first: "a".into(),
+                second: "b".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn merged_public_inputs() {
+        // def main(field a, field b, field c, private field d) -> field:
+        //      assert(a == b)
+        //      assert(b == c)
+        //      assert(a == d)
+        //      return b + c
+
+        // with the option, b and c are merged into a:
+        // def main(field a, private field d) -> field:
+        //      assert(a == d)
+        //      return a + a
+
+        let field = |id: &'static str| FieldElementExpression::Identifier(id.into());
+        let parameter = |id: &'static str, private| DeclarationParameter {
+            id: DeclarationVariable::field_element(id),
+            private,
+        };
+        let signature = |count| {
+            DeclarationSignature::new()
+                .inputs(vec![DeclarationType::FieldElement; count])
+                .outputs(vec![DeclarationType::FieldElement])
+        };
+        let program = |main: TypedFunction<'static, Bn128Field>| TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main.signature.clone()),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let main = TypedFunction {
+            arguments: vec![
+                parameter("a", false),
+                parameter("b", false),
+                parameter("c", false),
+                parameter("d", true),
+            ],
+            statements: vec![
+                TypedStatement::Assertion(BooleanExpression::FieldEq(
+                    box field("a"),
+                    box field("b"),
+                )),
+                TypedStatement::Assertion(BooleanExpression::FieldEq(
+                    box field("b"),
+                    box field("c"),
+                )),
+                TypedStatement::Assertion(BooleanExpression::FieldEq(
+                    box field("a"),
+                    box field("d"),
+                )),
+                TypedStatement::Return(vec![FieldElementExpression::Add(
+                    box field("b"),
+                    box field("c"),
+                )
+                .into()]),
+            ],
+            signature: signature(4),
+        };
+
+        let expected = TypedFunction {
+            arguments: vec![parameter("a", false), parameter("d", true)],
+            statements: vec![
+                TypedStatement::Assertion(BooleanExpression::FieldEq(
+                    box field("a"),
+                    box field("d"),
+                )),
+                TypedStatement::Return(vec![FieldElementExpression::Add(
+                    box field("a"),
+                    box field("a"),
+                )
+                .into()]),
+            ],
+            signature: signature(2),
+        };
+
+        let config = ReducerConfig {
+            merge_public_inputs: true,
+            ..ReducerConfig::default()
+        };
+
+        let (reduced, _, warnings) = reduce_program_with_report(program(main), &config).unwrap();
+
+        assert_eq!(reduced, program(expected));
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::MergedPublicInput {
+                    kept: "a".into(),
+                    removed: "b".into()
+                },
+                Warning::MergedPublicInput {
+                    kept: "a".into(),
+                    removed: "c".into()
+                }
+            ]
+        );
+        assert_eq!(
+            reduced
+                .abi()
+                .inputs
+                .into_iter()
+                .map(|input| (input.name, input.public))
+                .collect::<Vec<_>>(),
+            vec![("a".to_string(), true), ("d".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn generic_arity_mismatch() {
+        // def foo<K>(field[K] a) -> field[K]:
+        //      return a
+        // def main():
+        //      field[1] b = foo::<1, 2>([42])
+        //      field[1] b = foo::<>([42])
+        //      return
+
+        // expected:
+        // Error: GenericArityMismatch
+
+        let foo_signature = DeclarationSignature::new()
+            .generics(vec![Some(
+                GenericIdentifier::with_name("K").index(0).into(),
+            )])
+            .inputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("K").index(0),
+            ))])
+            .outputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("K").index(0),
+            ))]);
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::array(
+                "a",
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("K").index(0),
+            )
+            .into()],
+            statements: vec![TypedStatement::Return(vec![
+                ArrayExpressionInner::Identifier("a".into())
+                    .annotate(Type::FieldElement, 1u32)
+                    .into(),
+            ])],
+            signature: foo_signature.clone(),
+        };
+
+        let program = |generics: Vec<Option<UExpression<'static, Bn128Field>>>| {
+            let main: TypedFunction<Bn128Field> = TypedFunction {
+                arguments: vec![],
+                statements: vec![
+                    TypedStatement::MultipleDefinition(
+                        vec![Variable::array("b", Type::FieldElement, 1u32).into()],
+                        TypedExpressionList::function_call(
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(foo_signature.clone()),
+                            generics,
+                            vec![ArrayExpressionInner::Value(
+                                vec![FieldElementExpression::Number(Bn128Field::from(42)).into()]
+                                    .into(),
+                            )
+                            .annotate(Type::FieldElement, 1u32)
+                            .into()],
+                        )
+                        .annotate(Types::new(vec![Type::array((Type::FieldElement, 1u32))])),
+                    ),
+                    TypedStatement::Return(vec![]),
+                ],
+                signature: DeclarationSignature::new(),
+            };
+
+            TypedProgram {
+                main: "main".into(),
+                modules: vec![(
+                    "main".into(),
+                    TypedModule {
+                        functions: vec![
+                            (
+                                DeclarationFunctionKey::with_location("main", "foo")
+                                    .signature(foo_signature.clone()),
+                                TypedFunctionSymbol::Here(foo.clone()),
+                            ),
+                            (
+                                DeclarationFunctionKey::with_location("main", "main")
+                                    .signature(DeclarationSignature::new()),
+                                TypedFunctionSymbol::Here(main),
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                        constants: Default::default(),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            }
+        };
+
+        // too many generics
+        assert_eq!(
+            reduce_program(
+                program(vec![Some(1u32.into()), Some(2u32.into())]),
+                &ReducerConfig::default()
+            ),
+            Err(Error::GenericArityMismatch(
+                "main/foo<K>(field[K]) -> field[K]".into(),
+                1,
+                2
+            ))
+        );
+
+        // too few generics
+        assert_eq!(
+            reduce_program(program(vec![]), &ReducerConfig::default()),
+            Err(Error::GenericArityMismatch(
+                "main/foo<K>(field[K]) -> field[K]".into(),
+                1,
+                0
+            ))
+        );
+
+        assert_eq!(
+            reduce_program(program(vec![]), &ReducerConfig::default())
+                .unwrap_err()
+                .to_string(),
+            "Function `main/foo<K>(field[K]) -> field[K]` expects 1 generic parameters, found 0"
+        );
+    }
+
+    #[test]
+    fn generic_shadowed_by_variable() {
+        // def foo<N>(field[N] a) -> field[N]:
+        //      u32 N = 3
+        //      return a
+        // def main():
+        //      field[1] b = foo::<1>([42])
+        //      return
+
+        // expected:
+        // Error: GenericShadowedByVariable
+
+        let foo_signature = DeclarationSignature::new()
+            .generics(vec![Some(
+                GenericIdentifier::with_name("N").index(0).into(),
+            )])
+            .inputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("N").index(0),
+            ))])
+            .outputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("N").index(0),
+            ))]);
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::array(
+                "a",
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("N").index(0),
+            )
+            .into()],
+            statements: vec![
+                TypedStatement::Declaration(Variable::uint("N", UBitwidth::B32)),
+                TypedStatement::Definition(
+                    Variable::uint("N", UBitwidth::B32).into(),
+                    UExpression::from(3u32).into(),
+                ),
+                TypedStatement::Return(vec![ArrayExpressionInner::Identifier("a".into())
+                    .annotate(Type::FieldElement, 1u32)
+                    .into()]),
+            ],
+            signature: foo_signature.clone(),
+        };
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::MultipleDefinition(
+                    vec![Variable::array("b", Type::FieldElement, 1u32).into()],
+                    TypedExpressionList::function_call(
+                        DeclarationFunctionKey::with_location("main", "foo")
+                            .signature(foo_signature.clone()),
+                        vec![Some(1u32.into())],
+                        vec![ArrayExpressionInner::Value(
+                            vec![FieldElementExpression::Number(Bn128Field::from(42)).into()]
+                                .into(),
+                        )
+                        .annotate(Type::FieldElement, 1u32)
+                        .into()],
+                    )
+                    .annotate(Types::new(vec![Type::array((Type::FieldElement, 1u32))])),
+                ),
+                TypedStatement::Return(vec![]),
+            ],
+            signature: DeclarationSignature::new(),
+        };
+
+        let program = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(foo_signature.clone()),
+                            TypedFunctionSymbol::Here(foo),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(DeclarationSignature::new()),
+                            TypedFunctionSymbol::Here(main),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        assert_eq!(
+            reduce_program(program, &ReducerConfig::default()),
+            Err(Error::GenericShadowedByVariable(
+                "main/foo<N>(field[N]) -> field[N]".into(),
+                "N".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn error_messages() {
+        let program = |main: TypedFunction<'static, Bn128Field>| TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main.signature.clone()),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        // def main():
+        //      for u32 i in 0..1048577 do
+        //      endfor
+        //      return
+        let main = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    (MAX_FOR_LOOP_SIZE as u32 + 1).into(),
+                    vec![],
+                ),
+                TypedStatement::Return(vec![]),
+            ],
+            signature: DeclarationSignature::new(),
+        };
+
+        assert_eq!(
+            reduce_program(program(main), &ReducerConfig::default())
+                .unwrap_err()
+                .to_string(),
+            "Found a loop of size 1048577, which is larger than the maximum allowed of 1048576. Check the loop bounds, especially for underflows"
+        );
+
+        // def main(u32 n):
+        //      for u32 i in 0..n do
+        //      endfor
+        //      return
+        let main = TypedFunction {
+            arguments: vec![DeclarationVariable::uint("n", UBitwidth::B32).into()],
+            statements: vec![
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    UExpressionInner::Identifier("n".into()).annotate(UBitwidth::B32),
+                    vec![],
+                ),
+                TypedStatement::Return(vec![]),
+            ],
+            signature: DeclarationSignature::new()
+                .inputs(vec![DeclarationType::Uint(UBitwidth::B32)]),
+        };
+
+        assert_eq!(
+            reduce_program(program(main), &ReducerConfig::default())
+                .unwrap_err()
+                .to_string(),
+            "Failed to unroll or inline program. Check that main function arguments aren't used as array size or for-loop bounds"
+        );
+    }
+
+    #[test]
+    fn recursion() {
+        // a program where each function returns the result of calling another one
+        let program = |calls: Vec<(&'static str, &'static str)>| {
+            let signature =
+                DeclarationSignature::new().outputs(vec![DeclarationType::FieldElement]);
+
+            let call = |callee: &'static str| {
+                FieldElementExpression::function_call(
+                    DeclarationFunctionKey::with_location("main", callee)
+                        .signature(signature.clone()),
+                    vec![],
+                    vec![],
+                )
+            };
+
+            let functions = calls
+                .into_iter()
+                .chain(std::iter::once(("main", "foo")))
+                .map(|(caller, callee)| {
+                    (
+                        DeclarationFunctionKey::with_location("main", caller)
+                            .signature(signature.clone()),
+                        TypedFunctionSymbol::Here(TypedFunction {
+                            arguments: vec![],
+                            statements: vec![TypedStatement::Return(vec![call(callee).into()])],
+                            signature: signature.clone(),
+                        }),
+                    )
+                })
+                .collect();
+
+            TypedProgram::<Bn128Field> {
+                main: "main".into(),
+                modules: vec![(
+                    "main".into(),
+                    TypedModule {
+                        functions,
+                        constants: Default::default(),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            }
+        };
+
+        // def foo() -> field:
+        //      return foo()
+        assert_eq!(
+            reduce_program(program(vec![("foo", "foo")]), &ReducerConfig::default()),
+            Err(Error::Recursion("main/foo() -> field".into()))
+        );
+
+        // def bar() -> field:
+        //      return foo()
+        // def foo() -> field:
+        //      return bar()
+        assert_eq!(
+            reduce_program(
+                program(vec![("foo", "bar"), ("bar", "foo")]),
+                &ReducerConfig::default()
+            ),
+            Err(Error::Recursion("main/foo() -> field".into()))
+        );
+    }
+
+    #[test]
+    fn max_loop_iterations() {
+        // def main(field a) -> field:
+        //      for u32 i in 0..N do
+        //          a = a * a
+        //      endfor
+        //      return a
+        let program = |n: u32| {
+            let signature = DeclarationSignature::new()
+                .inputs(vec![DeclarationType::FieldElement])
+                .outputs(vec![DeclarationType::FieldElement]);
+
+            let main: TypedFunction<Bn128Field> = TypedFunction {
+                arguments: vec![DeclarationVariable::field_element("a").into()],
+                statements: vec![
+                    TypedStatement::For(
+                        Variable::uint("i", UBitwidth::B32),
+                        0u32.into(),
+                        n.into(),
+                        vec![TypedStatement::Definition(
+                            Variable::field_element("a").into(),
+                            FieldElementExpression::Mult(
+                                box FieldElementExpression::Identifier("a".into()),
+                                box FieldElementExpression::Identifier("a".into()),
+                            )
+                            .into(),
+                        )],
+                    ),
+                    TypedStatement::Return(vec![
+                        FieldElementExpression::Identifier("a".into()).into()
+                    ]),
+                ],
+                signature: signature.clone(),
+            };
+
+            TypedProgram {
+                main: "main".into(),
+                modules: vec![(
+                    "main".into(),
+                    TypedModule {
+                        functions: vec![(
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(signature),
+                            TypedFunctionSymbol::Here(main),
+                        )]
+                        .into_iter()
+                        .collect(),
+                        constants: Default::default(),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            }
+        };
+
+        let config = ReducerConfig {
+            max_loop_iterations: 3,
+            ..ReducerConfig::default()
+        };
+
+        // at the limit
+        assert!(reduce_program(program(3), &config).is_ok());
+
+        // over the limit
+        assert_eq!(
+            reduce_program(program(4), &config),
+            Err(Error::LoopTooLarge(4, 3))
+        );
+    }
+
+    #[test]
+    fn large_loop_warning() {
+        // def main(field a) -> field:
+        //      for u32 i in 0..N do
+        //          a = a * a
+        //      endfor
+        //      return a
+        let program = |n: u32| {
+            let signature = DeclarationSignature::new()
+                .inputs(vec![DeclarationType::FieldElement])
+                .outputs(vec![DeclarationType::FieldElement]);
+
+            let main: TypedFunction<Bn128Field> = TypedFunction {
+                arguments: vec![DeclarationVariable::field_element("a").into()],
+                statements: vec![
+                    TypedStatement::For(
+                        Variable::uint("i", UBitwidth::B32),
+                        0u32.into(),
+                        n.into(),
+                        vec![TypedStatement::Definition(
+                            Variable::field_element("a").into(),
+                            FieldElementExpression::Mult(
+                                box FieldElementExpression::Identifier("a".into()),
+                                box FieldElementExpression::Identifier("a".into()),
+                            )
+                            .into(),
+                        )],
+                    ),
+                    TypedStatement::Return(vec![
+                        FieldElementExpression::Identifier("a".into()).into()
+                    ]),
+                ],
+                signature: signature.clone(),
+            };
+
+            TypedProgram {
+                main: "main".into(),
+                modules: vec![(
+                    "main".into(),
+                    TypedModule {
+                        functions: vec![(
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(signature),
+                            TypedFunctionSymbol::Here(main),
+                        )]
+                        .into_iter()
+                        .collect(),
+                        constants: Default::default(),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            }
+        };
+
+        let config = ReducerConfig {
+            max_loop_iterations: 5,
+            large_loop_iterations: 2,
+            ..ReducerConfig::default()
+        };
+
+        // at the soft limit
+        let (_, _, warnings) = reduce_program_with_report(program(2), &config).unwrap();
+        assert_eq!(warnings, vec![]);
+
+        // between the soft and the hard limits
+        let (_, _, warnings) = reduce_program_with_report(program(3), &config).unwrap();
+        assert_eq!(
+            warnings,
+            vec![Warning::LargeLoop {
+                variable: "i".into(),
+                iterations: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn empty_ranges() {
+        // def main(field a) -> field:
+        //      for u32 i in FROM..TO do
+        //          a = a * a
+        //      endfor
+        //      return a
+        let program = |from: u32, to: u32| {
+            let signature = DeclarationSignature::new()
+                .inputs(vec![DeclarationType::FieldElement])
+                .outputs(vec![DeclarationType::FieldElement]);
+
+            let main: TypedFunction<Bn128Field> = TypedFunction {
+                arguments: vec![DeclarationVariable::field_element("a").into()],
+                statements: vec![
+                    TypedStatement::For(
+                        Variable::uint("i", UBitwidth::B32),
+                        from.into(),
+                        to.into(),
+                        vec![TypedStatement::Definition(
+                            Variable::field_element("a").into(),
+                            FieldElementExpression::Mult(
+                                box FieldElementExpression::Identifier("a".into()),
+                                box FieldElementExpression::Identifier("a".into()),
+                            )
+                            .into(),
+                        )],
+                    ),
+                    TypedStatement::Return(vec![
+                        FieldElementExpression::Identifier("a".into()).into()
+                    ]),
+                ],
+                signature: signature.clone(),
+            };
+
+            TypedProgram {
+                main: "main".into(),
+                modules: vec![(
+                    "main".into(),
+                    TypedModule {
+                        functions: vec![(
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(signature),
+                            TypedFunctionSymbol::Here(main),
+                        )]
+                        .into_iter()
+                        .collect(),
+                        constants: Default::default(),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            }
+        };
+
+        // a range whose end is not after its start generates no copy of the body
+        for &(from, to) in &[(0, 0), (3, 3), (5, 2)] {
+            let (_, stats, warnings) =
+                reduce_program_with_report(program(from, to), &ReducerConfig::default()).unwrap();
+
+            // only the return statement is left
+            assert_eq!(stats.statement_count, 1);
+            assert_eq!(stats.unrolled_iterations, 0);
+            assert_eq!(warnings, vec![]);
+        }
+
+        // all empty ranges reduce to the same program
+        let reduced =
+            |from, to| reduce_program(program(from, to), &ReducerConfig::default()).unwrap();
+
+        assert!(reduced(0, 0).alpha_eq(&reduced(5, 2)));
+
+        // a non-empty range generates one copy per iteration
+        let (_, stats, _) =
+            reduce_program_with_report(program(2, 5), &ReducerConfig::default()).unwrap();
+
+        assert_eq!(stats.statement_count, 4);
+        assert_eq!(stats.unrolled_iterations, 3);
+    }
+
+    #[test]
+    fn reduction_stats() {
+        // def bar(field a) -> field:
+        //      return a * a
+        // def foo(field a) -> field:
+        //      return bar(a)
+        // def main(field a) -> field:
+        //      for u32 i in 0..3 do
+        //          a = foo(a)
+        //      endfor
+        //      return a
+
+        // expected: 3 iterations, each inlining `foo` and `bar` inside it
+
+        let signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let a = || FieldElementExpression::Identifier("a".into());
+
+        let call = |callee: &'static str| {
+            FieldElementExpression::function_call(
+                DeclarationFunctionKey::with_location("main", callee).signature(signature.clone()),
+                vec![],
+                vec![a().into()],
+            )
+        };
+
+        let function = |statements| {
+            TypedFunctionSymbol::Here(TypedFunction {
+                arguments: vec![DeclarationVariable::field_element("a").into()],
+                statements,
+                signature: signature.clone(),
+            })
+        };
+
+        let p: TypedProgram<Bn128Field> = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (
+                            DeclarationFunctionKey::with_location("main", "bar")
+                                .signature(signature.clone()),
+                            function(vec![TypedStatement::Return(vec![
+                                FieldElementExpression::Mult(box a(), box a()).into(),
+                            ])]),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(signature.clone()),
+                            function(vec![TypedStatement::Return(vec![call("bar").into()])]),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(signature.clone()),
+                            function(vec![
+                                TypedStatement::For(
+                                    Variable::uint("i", UBitwidth::B32),
+                                    0u32.into(),
+                                    3u32.into(),
+                                    vec![TypedStatement::Definition(
+                                        Variable::field_element("a").into(),
+                                        call("foo").into(),
+                                    )],
+                                ),
+                                TypedStatement::Return(vec![a().into()]),
+                            ]),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let (_, stats, _) = reduce_program_with_report(p, &ReducerConfig::default()).unwrap();
+
+        assert_eq!(stats.inlined_calls, 6);
+        assert_eq!(stats.max_call_depth, 2);
+        assert_eq!(stats.unrolled_iterations, 3);
+    }
+
+    #[test]
+    fn embed_call() {
+        // from "EMBED" import u32_to_field
+        // def main(u32 a) -> field:
+        //      field b = u32_to_field(a)
+        //      return b
+
+        // expected: the embed call is kept as is
+
+        let embed_key = DeclarationFunctionKey::with_location("main", "u32_to_field")
+            .signature(FlatEmbed::U32ToField.signature());
+
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::Uint(UBitwidth::B32)])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let main = |call: TypedExpressionListInner<'static, Bn128Field>| TypedFunction {
+            arguments: vec![DeclarationVariable::uint("a", UBitwidth::B32).into()],
+            statements: vec![
+                TypedStatement::MultipleDefinition(
+                    vec![Variable::field_element("b").into()],
+                    call.annotate(Types::new(vec![Type::FieldElement])),
+                ),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("b".into()).into()]),
+            ],
+            signature: main_signature.clone(),
+        };
+
+        let a: TypedExpression<Bn128Field> = UExpressionInner::Identifier("a".into())
+            .annotate(UBitwidth::B32)
+            .into();
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (
+                            embed_key.clone(),
+                            TypedFunctionSymbol::Flat(FlatEmbed::U32ToField),
+                        ),
+                        (
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(main_signature.clone()),
+                            TypedFunctionSymbol::Here(main(
+                                TypedExpressionListInner::FunctionCall(
+                                    FunctionCallExpression::new(embed_key, vec![], vec![a.clone()]),
+                                ),
+                            )),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let expected: TypedProgram<Bn128Field> = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main")
+                            .signature(main_signature.clone()),
+                        TypedFunctionSymbol::Here(main(TypedExpressionListInner::EmbedCall(
+                            FlatEmbed::U32ToField,
+                            vec![],
+                            vec![a],
+                        ))),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        assert_eq!(
+            reduce_program(p, &ReducerConfig::default()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn keep_non_generic_call() {
+        // def foo(field a) -> field:
+        //      return a * a
+        // def main(field a) -> field:
+        //      field b = foo(a)
+        //      return b
+
+        // expected with the `NeverForNonGeneric` policy: the program is unchanged
+
+        let signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let foo_key =
+            DeclarationFunctionKey::with_location("main", "foo").signature(signature.clone());
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![FieldElementExpression::Mult(
+                box FieldElementExpression::Identifier("a".into()),
+                box FieldElementExpression::Identifier("a".into()),
+            )
+            .into()])],
+            signature: signature.clone(),
+        };
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![
+                TypedStatement::MultipleDefinition(
+                    vec![Variable::field_element("b").into()],
+                    TypedExpressionList::function_call(
+                        foo_key.clone(),
+                        vec![],
+                        vec![FieldElementExpression::Identifier("a".into()).into()],
+                    )
+                    .annotate(Types::new(vec![Type::FieldElement])),
+                ),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("b".into()).into()]),
+            ],
+            signature: signature.clone(),
         };
 
         let p = TypedProgram {
@@ -1405,18 +4594,94 @@ mod tests {
                 "main".into(),
                 TypedModule {
                     functions: vec![
+                        (foo_key, TypedFunctionSymbol::Here(foo)),
                         (
-                            DeclarationFunctionKey::with_location("main", "bar")
-                                .signature(bar_signature.clone()),
-                            TypedFunctionSymbol::Here(bar),
-                        ),
-                        (
-                            DeclarationFunctionKey::with_location("main", "foo")
-                                .signature(foo_signature.clone()),
-                            TypedFunctionSymbol::Here(foo),
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(signature),
+                            TypedFunctionSymbol::Here(main),
                         ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let config = ReducerConfig {
+            inline_policy: InlinePolicy::NeverForNonGeneric,
+            ..ReducerConfig::default()
+        };
+
+        let (reduced, stats, _) = reduce_program_with_report(p.clone(), &config).unwrap();
+
+        assert_eq!(reduced, p);
+        assert_eq!(stats.inlined_calls, 0);
+    }
+
+    #[test]
+    fn partial_reduction() {
+        // def foo(field a) -> field:
+        //      return a * a
+        // def main(field a) -> field:
+        //      for u32 i in 0..2 do
+        //          a = foo(a)
+        //      endfor
+        //      return a
+
+        // expected with a maximum of one loop iteration: `foo` is reduced, `main` fails
+
+        let signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let foo_key =
+            DeclarationFunctionKey::with_location("main", "foo").signature(signature.clone());
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(vec![FieldElementExpression::Mult(
+                box FieldElementExpression::Identifier("a".into()),
+                box FieldElementExpression::Identifier("a".into()),
+            )
+            .into()])],
+            signature: signature.clone(),
+        };
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    2u32.into(),
+                    vec![TypedStatement::MultipleDefinition(
+                        vec![Variable::field_element("a").into()],
+                        TypedExpressionList::function_call(
+                            foo_key.clone(),
+                            vec![],
+                            vec![FieldElementExpression::Identifier("a".into()).into()],
+                        )
+                        .annotate(Types::new(vec![Type::FieldElement])),
+                    )],
+                ),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
+            ],
+            signature: signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![
+                        (foo_key.clone(), TypedFunctionSymbol::Here(foo.clone())),
                         (
-                            DeclarationFunctionKey::with_location("main", "main"),
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(signature),
                             TypedFunctionSymbol::Here(main),
                         ),
                     ]
@@ -1429,108 +4694,86 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        let expected: TypedProgram<Bn128Field> = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(foo_key, TypedFunctionSymbol::Here(foo))]
+                        .into_iter()
+                        .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let config = ReducerConfig {
+            max_loop_iterations: 1,
+            ..ReducerConfig::default()
+        };
 
-        let expected_main = TypedFunction {
-            arguments: vec![],
+        assert_eq!(
+            reduce_program_partial(p, &config),
+            ReduceOutcome {
+                program: expected,
+                error: Some(Error::LoopTooLarge(2, 1))
+            }
+        );
+    }
+
+    #[test]
+    fn standalone_function() {
+        // def foo<K>(field a) -> field:
+        //      for u32 i in 0..K do
+        //          a = a * a
+        //      endfor
+        //      return a
+
+        // expected with K == 2:
+        // def foo<K>(field a_0) -> field:
+        //      a_4 = a_0 * a_0
+        //      a_5 = a_4 * a_4
+        //      return a_5
+
+        let signature = DeclarationSignature::new()
+            .generics(vec![Some(
+                GenericIdentifier::with_name("K").index(0).into(),
+            )])
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
             statements: vec![
-                TypedStatement::PushCallLog(
-                    DeclarationFunctionKey::with_location("main", "foo")
-                        .signature(foo_signature.clone()),
-                    GGenericsAssignment(
-                        vec![(GenericIdentifier::with_name("K").index(0), 1)]
-                            .into_iter()
-                            .collect(),
-                    ),
-                ),
-                TypedStatement::PushCallLog(
-                    DeclarationFunctionKey::with_location("main", "bar")
-                        .signature(foo_signature.clone()),
-                    GGenericsAssignment(
-                        vec![(GenericIdentifier::with_name("K").index(0), 2)]
-                            .into_iter()
-                            .collect(),
-                    ),
-                ),
-                TypedStatement::Definition(
-                    Variable::array(Identifier::from("a").version(1), Type::FieldElement, 2u32)
-                        .into(),
-                    ArrayExpressionInner::Value(
-                        vec![
-                            TypedExpressionOrSpread::Spread(
-                                ArrayExpressionInner::Value(
-                                    vec![TypedExpressionOrSpread::Expression(
-                                        FieldElementExpression::Number(Bn128Field::from(1)).into(),
-                                    )]
-                                    .into(),
-                                )
-                                .annotate(Type::FieldElement, 1u32)
-                                .into(),
-                            ),
-                            FieldElementExpression::Number(Bn128Field::from(0)).into(),
-                        ]
-                        .into(),
-                    )
-                    .annotate(Type::FieldElement, 2u32)
-                    .into(),
-                ),
-                TypedStatement::Definition(
-                    Variable::array(
-                        Identifier::from(CoreIdentifier::Call(0)).version(1),
-                        Type::FieldElement,
-                        2u32,
-                    )
-                    .into(),
-                    ArrayExpressionInner::Identifier(Identifier::from("a").version(1))
-                        .annotate(Type::FieldElement, 2u32)
-                        .into(),
-                ),
-                TypedStatement::PopCallLog,
-                TypedStatement::Definition(
-                    Variable::array("ret", Type::FieldElement, 1u32).into(),
-                    ArrayExpressionInner::Slice(
-                        box ArrayExpressionInner::Identifier(
-                            Identifier::from(CoreIdentifier::Call(0)).version(1),
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
+                    vec![TypedStatement::Definition(
+                        Variable::field_element("a").into(),
+                        FieldElementExpression::Mult(
+                            box FieldElementExpression::Identifier("a".into()),
+                            box FieldElementExpression::Identifier("a".into()),
                         )
-                        .annotate(Type::FieldElement, 2u32),
-                        box 0u32.into(),
-                        box 1u32.into(),
-                    )
-                    .annotate(Type::FieldElement, 1u32)
-                    .into(),
-                ),
-                TypedStatement::Definition(
-                    Variable::array(
-                        Identifier::from(CoreIdentifier::Call(0)),
-                        Type::FieldElement,
-                        1u32,
-                    )
-                    .into(),
-                    ArrayExpressionInner::Identifier("ret".into())
-                        .annotate(Type::FieldElement, 1u32)
-                        .into(),
-                ),
-                TypedStatement::PopCallLog,
-                TypedStatement::Definition(
-                    Variable::array("b", Type::FieldElement, 1u32).into(),
-                    ArrayExpressionInner::Identifier(Identifier::from(CoreIdentifier::Call(0)))
-                        .annotate(Type::FieldElement, 1u32)
                         .into(),
+                    )],
                 ),
-                TypedStatement::Return(vec![]),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
             ],
-            signature: DeclarationSignature::new(),
+            signature: signature.clone(),
         };
 
-        let expected = TypedProgram {
+        let p = TypedProgram {
             main: "main".into(),
             modules: vec![(
                 "main".into(),
                 TypedModule {
                     functions: vec![(
-                        DeclarationFunctionKey::with_location("main", "main")
-                            .signature(DeclarationSignature::new()),
-                        TypedFunctionSymbol::Here(expected_main),
+                        DeclarationFunctionKey::with_location("main", "foo")
+                            .signature(signature.clone()),
+                        TypedFunctionSymbol::Here(foo.clone()),
                     )]
                     .into_iter()
                     .collect(),
@@ -1541,19 +4784,46 @@ mod tests {
             .collect(),
         };
 
-        assert_eq!(reduced.unwrap(), expected);
+        let a =
+            |version| FieldElementExpression::Identifier(Identifier::from("a").version(version));
+
+        let expected = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![
+                TypedStatement::Definition(
+                    Variable::field_element(Identifier::from("a").version(4)).into(),
+                    FieldElementExpression::Mult(box a(0), box a(0)).into(),
+                ),
+                TypedStatement::Definition(
+                    Variable::field_element(Identifier::from("a").version(5)).into(),
+                    FieldElementExpression::Mult(box a(4), box a(4)).into(),
+                ),
+                TypedStatement::Return(vec![a(5).into()]),
+            ],
+            signature: signature.clone(),
+        };
+
+        assert_eq!(
+            reduce_function(foo.clone(), vec![2], &p, &ReducerConfig::default()),
+            Ok(expected)
+        );
+
+        assert_eq!(
+            reduce_function(foo, vec![], &p, &ReducerConfig::default()),
+            Err(Error::GenericArityMismatch(signature.to_string(), 1, 0))
+        );
     }
 
     #[test]
-    fn incompatible() {
+    fn no_progress() {
         // def foo<K>(field[K] a) -> field[K]:
         //      return a
-        // def main():
-        //      field[1] b = foo([])
-        //      return
+        // def main(u32 n, field[1] a) -> field[1]:
+        //      a = foo::<n>(a)
+        //      return a
 
         // expected:
-        // Error: Incompatible
+        // Error: UnresolvableSize, as `n` never becomes constant
 
         let foo_signature = DeclarationSignature::new()
             .generics(vec![Some(
@@ -1583,24 +4853,41 @@ mod tests {
             signature: foo_signature.clone(),
         };
 
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![
+                DeclarationType::Uint(UBitwidth::B32),
+                DeclarationType::array((DeclarationType::FieldElement, 1u32)),
+            ])
+            .outputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                1u32,
+            ))]);
+
         let main: TypedFunction<Bn128Field> = TypedFunction {
-            arguments: vec![],
+            arguments: vec![
+                DeclarationVariable::uint("n", UBitwidth::B32).into(),
+                DeclarationVariable::array("a", DeclarationType::FieldElement, 1u32).into(),
+            ],
             statements: vec![
                 TypedStatement::MultipleDefinition(
-                    vec![Variable::array("b", Type::FieldElement, 1u32).into()],
+                    vec![Variable::array("a", Type::FieldElement, 1u32).into()],
                     TypedExpressionList::function_call(
                         DeclarationFunctionKey::with_location("main", "foo")
                             .signature(foo_signature.clone()),
-                        vec![None],
-                        vec![ArrayExpressionInner::Value(vec![].into())
-                            .annotate(Type::FieldElement, 0u32)
+                        vec![Some(
+                            UExpressionInner::Identifier("n".into()).annotate(UBitwidth::B32),
+                        )],
+                        vec![ArrayExpressionInner::Identifier("a".into())
+                            .annotate(Type::FieldElement, 1u32)
                             .into()],
                     )
                     .annotate(Types::new(vec![Type::array((Type::FieldElement, 1u32))])),
                 ),
-                TypedStatement::Return(vec![]),
+                TypedStatement::Return(vec![ArrayExpressionInner::Identifier("a".into())
+                    .annotate(Type::FieldElement, 1u32)
+                    .into()]),
             ],
-            signature: DeclarationSignature::new().inputs(vec![]).outputs(vec![]),
+            signature: main_signature.clone(),
         };
 
         let p = TypedProgram {
@@ -1611,13 +4898,12 @@ mod tests {
                     functions: vec![
                         (
                             DeclarationFunctionKey::with_location("main", "foo")
-                                .signature(foo_signature.clone()),
+                                .signature(foo_signature),
                             TypedFunctionSymbol::Here(foo),
                         ),
                         (
-                            DeclarationFunctionKey::with_location("main", "main").signature(
-                                DeclarationSignature::new().inputs(vec![]).outputs(vec![]),
-                            ),
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(main_signature),
                             TypedFunctionSymbol::Here(main),
                         ),
                     ]
@@ -1630,11 +4916,81 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        assert_eq!(
+            reduce_program(p, &ReducerConfig::default()),
+            Err(Error::UnresolvableSize("n".into()))
+        );
+    }
+
+    #[test]
+    fn max_rounds() {
+        // def main(field a) -> field:
+        //      for u32 i in 0..2 do
+        //          for u32 j in 0..2 do
+        //              a = a * a
+        //          endfor
+        //      endfor
+        //      return a
+
+        // the inner loops are unrolled in a second round
+
+        let signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![
+                TypedStatement::For(
+                    Variable::uint("i", UBitwidth::B32),
+                    0u32.into(),
+                    2u32.into(),
+                    vec![TypedStatement::For(
+                        Variable::uint("j", UBitwidth::B32),
+                        0u32.into(),
+                        2u32.into(),
+                        vec![TypedStatement::Definition(
+                            Variable::field_element("a").into(),
+                            FieldElementExpression::Mult(
+                                box FieldElementExpression::Identifier("a".into()),
+                                box FieldElementExpression::Identifier("a".into()),
+                            )
+                            .into(),
+                        )],
+                    )],
+                ),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier("a".into()).into()]),
+            ],
+            signature: signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main").signature(signature),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let config = |max| ReducerConfig {
+            max_rounds: Some(max),
+            ..ReducerConfig::default()
+        };
 
         assert_eq!(
-            reduced,
-            Err(Error::Incompatible("Call site `main/foo<_>(field[0]) -> field[1]` incompatible with declaration `main/foo<K>(field[K]) -> field[K]`".into()))
+            reduce_program(p.clone(), &config(1)),
+            Err(Error::DidNotConverge(1))
         );
+        assert!(reduce_program(p, &config(2)).is_ok());
     }
 }
@@ -0,0 +1,67 @@
+// Detect boolean variables which are defined by a function but never read.
+// A computed boolean which is never used, such as the result of a comparison, most likely means that an assertion was dropped.
+
+use crate::typed_absy::folder::*;
+use crate::typed_absy::types::Type;
+use crate::typed_absy::{
+    CoreIdentifier, Identifier, TypedAssignee, TypedExpression, TypedFunction, TypedStatement,
+};
+use std::collections::HashSet;
+use zokrates_field::Field;
+
+#[derive(Default)]
+pub struct UnusedBooleansDetector<'ast> {
+    defined: Vec<&'ast str>,
+    used: HashSet<&'ast str>,
+}
+
+impl<'ast> UnusedBooleansDetector<'ast> {
+    // return the names of the boolean variables of `f` which are never read, in definition order
+    pub fn detect<T: Field>(f: &TypedFunction<'ast, T>) -> Vec<&'ast str> {
+        let mut detector = UnusedBooleansDetector::default();
+
+        detector.fold_function(f.clone());
+
+        let used = detector.used;
+
+        detector
+            .defined
+            .into_iter()
+            .filter(|name| !used.contains(name))
+            .collect()
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for UnusedBooleansDetector<'ast> {
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        match s {
+            // declaring a variable does not read it
+            TypedStatement::Declaration(v) => vec![TypedStatement::Declaration(v)],
+            // neither does assigning a boolean to it
+            TypedStatement::Definition(
+                TypedAssignee::Identifier(v),
+                TypedExpression::Boolean(e),
+            ) if v._type == Type::Boolean => {
+                if let CoreIdentifier::Source(name) = v.id.id {
+                    if !self.defined.contains(&name) {
+                        self.defined.push(name);
+                    }
+                }
+
+                let e = self.fold_boolean_expression(e);
+                vec![TypedStatement::Definition(
+                    TypedAssignee::Identifier(v),
+                    e.into(),
+                )]
+            }
+            s => fold_statement(self, s),
+        }
+    }
+
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        if let CoreIdentifier::Source(name) = n.id {
+            self.used.insert(name);
+        }
+        n
+    }
+}
@@ -0,0 +1,59 @@
+// Detect generic parameters which are declared by a function but appear neither in its signature nor in its body.
+// The caller still has to provide a value for such parameters (explicitly or through inference), which is most likely a mistake.
+
+use crate::typed_absy::folder::*;
+use crate::typed_absy::types::DeclarationConstant;
+use crate::typed_absy::{CoreIdentifier, DeclarationFunctionKey, Identifier, TypedFunction};
+use std::collections::HashSet;
+use zokrates_field::Field;
+
+#[derive(Default)]
+pub struct UnusedGenericsDetector<'ast> {
+    used: HashSet<&'ast str>,
+}
+
+impl<'ast> UnusedGenericsDetector<'ast> {
+    // return the names of the generic parameters of `f` which are never used, in declaration order
+    pub fn detect<T: Field>(f: &TypedFunction<'ast, T>) -> Vec<&'ast str> {
+        let mut detector = UnusedGenericsDetector::default();
+
+        detector.fold_function(f.clone());
+
+        f.signature
+            .generics
+            .iter()
+            .filter_map(|g| match g {
+                Some(DeclarationConstant::Generic(g)) => Some(g.name),
+                _ => None,
+            })
+            .filter(|name| !detector.used.contains(name))
+            .collect()
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for UnusedGenericsDetector<'ast> {
+    // the signatures of the functions we call refer to their own generic parameters, not ours
+    fn fold_declaration_function_key(
+        &mut self,
+        key: DeclarationFunctionKey<'ast>,
+    ) -> DeclarationFunctionKey<'ast> {
+        key
+    }
+
+    fn fold_declaration_constant(
+        &mut self,
+        c: DeclarationConstant<'ast>,
+    ) -> DeclarationConstant<'ast> {
+        if let DeclarationConstant::Generic(g) = &c {
+            self.used.insert(g.name);
+        }
+        c
+    }
+
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        if let CoreIdentifier::Source(name) = n.id {
+            self.used.insert(name);
+        }
+        n
+    }
+}
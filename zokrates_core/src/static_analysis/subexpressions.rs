@@ -0,0 +1,376 @@
+//! Module containing common subexpression elimination on the reduced program
+//!
+//! Each product of field elements costs a constraint. On the SSA program, two structurally equal products hold the
+//! same value, so a product occurring several times in a function is computed once into a fresh variable which
+//! replaces all of its occurrences.
+//! Uint operations are not shared, as storing their result in a variable prevents the uint optimizer from merging
+//! them with the surrounding operations, which costs more constraints than it saves.
+//! Blocks and loops are left untouched. A write to an array element or a struct member does not create a new version
+//! of the variable, so the products reading this variable are not shared across such a write.
+
+use crate::static_analysis::composite_writes::{reads, written_variables};
+use crate::typed_absy::folder::*;
+use crate::typed_absy::*;
+use std::collections::HashMap;
+use zokrates_field::Field;
+
+#[derive(Default)]
+pub struct SubexpressionEliminator<'ast, T> {
+    // the number of occurrences of each subexpression in the current function
+    occurrences: HashMap<TypedExpression<'ast, T>, usize>,
+    // the variable holding the value of each shared subexpression
+    shared: HashMap<TypedExpression<'ast, T>, Identifier<'ast>>,
+    // the definitions of the shared subexpressions found in the current statement
+    definitions: Vec<TypedStatement<'ast, T>>,
+    // the number of shared subexpressions so far
+    count: usize,
+}
+
+impl<'ast, T: Field> SubexpressionEliminator<'ast, T> {
+    pub fn eliminate(p: TypedProgram<'ast, T>) -> TypedProgram<'ast, T> {
+        SubexpressionEliminator::default().fold_program(p)
+    }
+
+    fn fresh(&mut self) -> Identifier<'ast> {
+        self.count += 1;
+        Identifier::from(CoreIdentifier::Subexpression(self.count - 1))
+    }
+
+    // whether `e` occurs several times in the current function
+    fn is_repeated(&self, e: &TypedExpression<'ast, T>) -> bool {
+        self.occurrences.get(e).cloned().unwrap_or(0) > 1
+    }
+}
+
+// products of non-constant field elements
+fn is_shareable_field<T>(e: &FieldElementExpression<T>) -> bool {
+    match e {
+        FieldElementExpression::Mult(left, right) => !matches!(
+            (&**left, &**right),
+            (FieldElementExpression::Number(..), _) | (_, FieldElementExpression::Number(..))
+        ),
+        _ => false,
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for SubexpressionEliminator<'ast, T> {
+    fn fold_function(&mut self, f: TypedFunction<'ast, T>) -> TypedFunction<'ast, T> {
+        let mut occurrences = Occurrences::default();
+        for s in &f.statements {
+            occurrences.fold_statement(s.clone());
+        }
+
+        self.occurrences = occurrences.0;
+        self.shared = HashMap::new();
+
+        let mut statements = vec![];
+        for s in f.statements {
+            let written = written_variables(&s);
+
+            let s = self.fold_statement(s);
+            statements.append(&mut self.definitions);
+            statements.extend(s);
+
+            // the values read from the written variables change, so they are not shared with the following statements
+            if !written.is_empty() {
+                self.shared.retain(|e, _| !reads(e.clone(), &written));
+            }
+        }
+
+        TypedFunction { statements, ..f }
+    }
+
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        match s {
+            TypedStatement::For(..) => vec![s],
+            s => fold_statement(self, s),
+        }
+    }
+
+    fn fold_block_expression<E: Fold<'ast, T>>(
+        &mut self,
+        block: BlockExpression<'ast, T, E>,
+    ) -> BlockExpression<'ast, T, E> {
+        block
+    }
+
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        let key: TypedExpression<'ast, T> = e.clone().into();
+
+        if !is_shareable_field(&e) || !self.is_repeated(&key) {
+            return fold_field_expression(self, e);
+        }
+
+        match self.shared.get(&key).cloned() {
+            Some(id) => FieldElementExpression::Identifier(id),
+            None => {
+                let e = fold_field_expression(self, e);
+                let id = self.fresh();
+                self.definitions.push(TypedStatement::Definition(
+                    Variable::field_element(id.clone()).into(),
+                    e.into(),
+                ));
+                self.shared.insert(key, id.clone());
+                FieldElementExpression::Identifier(id)
+            }
+        }
+    }
+}
+
+// the number of occurrences of each shareable product outside of blocks and loops
+#[derive(Default)]
+struct Occurrences<'ast, T>(HashMap<TypedExpression<'ast, T>, usize>);
+
+impl<'ast, T: Field> Folder<'ast, T> for Occurrences<'ast, T> {
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        match s {
+            TypedStatement::For(..) => vec![s],
+            s => fold_statement(self, s),
+        }
+    }
+
+    fn fold_block_expression<E: Fold<'ast, T>>(
+        &mut self,
+        block: BlockExpression<'ast, T, E>,
+    ) -> BlockExpression<'ast, T, E> {
+        block
+    }
+
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        if is_shareable_field(&e) {
+            *self.0.entry(e.clone().into()).or_insert(0) += 1;
+        }
+        fold_field_expression(self, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    fn field(id: &'static str, version: usize) -> FieldElementExpression<'static, Bn128Field> {
+        FieldElementExpression::Identifier(Identifier::from(id).version(version))
+    }
+
+    fn function(
+        statements: Vec<TypedStatement<'static, Bn128Field>>,
+    ) -> TypedFunction<'static, Bn128Field> {
+        TypedFunction {
+            arguments: vec![
+                DeclarationVariable::field_element("a").into(),
+                DeclarationVariable::field_element("b").into(),
+            ],
+            statements,
+            signature: DeclarationSignature::new()
+                .inputs(vec![
+                    DeclarationType::FieldElement,
+                    DeclarationType::FieldElement,
+                ])
+                .outputs(vec![DeclarationType::FieldElement]),
+        }
+    }
+
+    #[test]
+    fn share_product() {
+        // c = a * b + a
+        // d = a * b + b
+        // return c * d
+
+        // expected:
+        // #SUBEXPRESSION_0 = a * b
+        // c = #SUBEXPRESSION_0 + a
+        // d = #SUBEXPRESSION_0 + b
+        // return c * d
+
+        let product = || FieldElementExpression::Mult(box field("a", 0), box field("b", 0));
+
+        let f = function(vec![
+            TypedStatement::Definition(
+                Variable::field_element("c").into(),
+                FieldElementExpression::Add(box product(), box field("a", 0)).into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("d").into(),
+                FieldElementExpression::Add(box product(), box field("b", 0)).into(),
+            ),
+            TypedStatement::Return(vec![FieldElementExpression::Mult(
+                box field("c", 0),
+                box field("d", 0),
+            )
+            .into()]),
+        ]);
+
+        let shared = Identifier::from(CoreIdentifier::Subexpression(0));
+
+        let expected = function(vec![
+            TypedStatement::Definition(
+                Variable::field_element(shared.clone()).into(),
+                product().into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("c").into(),
+                FieldElementExpression::Add(
+                    box FieldElementExpression::Identifier(shared.clone()),
+                    box field("a", 0),
+                )
+                .into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("d").into(),
+                FieldElementExpression::Add(
+                    box FieldElementExpression::Identifier(shared),
+                    box field("b", 0),
+                )
+                .into(),
+            ),
+            TypedStatement::Return(vec![FieldElementExpression::Mult(
+                box field("c", 0),
+                box field("d", 0),
+            )
+            .into()]),
+        ]);
+
+        assert_eq!(
+            SubexpressionEliminator::default().fold_function(f),
+            expected
+        );
+    }
+
+    #[test]
+    fn keep_uint_operation() {
+        // u32 c = x + y
+        // assert(c == (x + y) ^ x)
+        // return
+
+        let uint =
+            |id: &'static str| UExpressionInner::Identifier(id.into()).annotate(UBitwidth::B32);
+        let sum = || UExpressionInner::Add(box uint("x"), box uint("y")).annotate(UBitwidth::B32);
+
+        let f = function(vec![
+            TypedStatement::Definition(Variable::uint("c", UBitwidth::B32).into(), sum().into()),
+            TypedStatement::Assertion(BooleanExpression::UintEq(
+                box uint("c"),
+                box UExpressionInner::Xor(box sum(), box uint("x")).annotate(UBitwidth::B32),
+            )),
+            TypedStatement::Return(vec![]),
+        ]);
+
+        assert_eq!(
+            SubexpressionEliminator::default().fold_function(f.clone()),
+            f
+        );
+    }
+
+    #[test]
+    fn different_versions() {
+        // c = a_1 * b
+        // d = a_2 * b
+        // return c + d
+
+        // `a_1 * b` and `a_2 * b` read different values, so nothing is shared
+
+        let f = function(vec![
+            TypedStatement::Definition(
+                Variable::field_element("c").into(),
+                FieldElementExpression::Mult(box field("a", 1), box field("b", 0)).into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("d").into(),
+                FieldElementExpression::Mult(box field("a", 2), box field("b", 0)).into(),
+            ),
+            TypedStatement::Return(vec![FieldElementExpression::Add(
+                box field("c", 0),
+                box field("d", 0),
+            )
+            .into()]),
+        ]);
+
+        assert_eq!(
+            SubexpressionEliminator::default().fold_function(f.clone()),
+            f
+        );
+    }
+
+    #[test]
+    fn element_write() {
+        // field[2] v = [a, b]
+        // c = v[0] * v[1]
+        // v[0] = b
+        // d = v[0] * v[1]
+        // return c + d
+
+        // expected:
+        // field[2] v = [a, b]
+        // #SUBEXPRESSION_0 = v[0] * v[1]
+        // c = #SUBEXPRESSION_0
+        // v[0] = b
+        // #SUBEXPRESSION_1 = v[0] * v[1]
+        // d = #SUBEXPRESSION_1
+        // return c + d
+
+        let v = || ArrayExpressionInner::Identifier("v".into()).annotate(Type::FieldElement, 2u32);
+        let product = || {
+            FieldElementExpression::Mult(
+                box FieldElementExpression::select(v(), 0u32),
+                box FieldElementExpression::select(v(), 1u32),
+            )
+        };
+
+        let definition = TypedStatement::Definition(
+            Variable::array("v", Type::FieldElement, 2u32).into(),
+            ArrayExpressionInner::Value(vec![field("a", 0).into(), field("b", 0).into()].into())
+                .annotate(Type::FieldElement, 2u32)
+                .into(),
+        );
+        let write = TypedStatement::Definition(
+            TypedAssignee::Select(
+                box Variable::array("v", Type::FieldElement, 2u32).into(),
+                box 0u32.into(),
+            ),
+            field("b", 0).into(),
+        );
+        let ret = TypedStatement::Return(vec![FieldElementExpression::Add(
+            box field("c", 0),
+            box field("d", 0),
+        )
+        .into()]);
+
+        let f = function(vec![
+            definition.clone(),
+            TypedStatement::Definition(Variable::field_element("c").into(), product().into()),
+            write.clone(),
+            TypedStatement::Definition(Variable::field_element("d").into(), product().into()),
+            ret.clone(),
+        ]);
+
+        let shared = |i| Identifier::from(CoreIdentifier::Subexpression(i));
+
+        let expected = function(vec![
+            definition,
+            TypedStatement::Definition(Variable::field_element(shared(0)).into(), product().into()),
+            TypedStatement::Definition(
+                Variable::field_element("c").into(),
+                FieldElementExpression::Identifier(shared(0)).into(),
+            ),
+            write,
+            TypedStatement::Definition(Variable::field_element(shared(1)).into(), product().into()),
+            TypedStatement::Definition(
+                Variable::field_element("d").into(),
+                FieldElementExpression::Identifier(shared(1)).into(),
+            ),
+            ret,
+        ]);
+
+        assert_eq!(
+            SubexpressionEliminator::default().fold_function(f),
+            expected
+        );
+    }
+}
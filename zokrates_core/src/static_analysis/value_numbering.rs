@@ -0,0 +1,265 @@
+//! Module containing value numbering on the reduced program
+//!
+//! On the SSA program, two variables defined by the same expression hold the same value, and so does a variable
+//! defined by an identity such as `a_2 = a_1 + 0`. Each such variable is given the value number of a canonical
+//! representative, its uses are rewritten to this representative and its definition is removed.
+//! Only basic types are handled. A write to an array element or a struct member does not create a new version of
+//! the variable, so the expressions reading this variable are forgotten after such a write.
+//! This builds on the copy eliminator, which runs before: the identities removed here can leave copies `a = b`,
+//! which are eliminated the same way.
+
+use crate::static_analysis::composite_writes::{reads, written_variables};
+use crate::static_analysis::copy_eliminator::copy_source;
+use crate::typed_absy::folder::*;
+use crate::typed_absy::*;
+use std::collections::HashMap;
+use zokrates_field::Field;
+
+#[derive(Default)]
+pub struct ValueNumbering<'ast, T> {
+    // the canonical representative of each eliminated identifier
+    representatives: HashMap<Identifier<'ast>, Identifier<'ast>>,
+    // the identifier holding the value of each expression defined so far
+    values: HashMap<TypedExpression<'ast, T>, Identifier<'ast>>,
+}
+
+impl<'ast, T: Field> ValueNumbering<'ast, T> {
+    pub fn apply(p: TypedProgram<'ast, T>) -> TypedProgram<'ast, T> {
+        ValueNumbering::default().fold_program(p)
+    }
+}
+
+// remove the identities `x + 0`, `0 + x`, `x * 1` and `1 * x` at the top level of a field element expression
+fn remove_identity<T: Field>(e: FieldElementExpression<T>) -> FieldElementExpression<T> {
+    match e {
+        FieldElementExpression::Add(box e, box FieldElementExpression::Number(n))
+        | FieldElementExpression::Add(box FieldElementExpression::Number(n), box e)
+            if n == T::zero() =>
+        {
+            remove_identity(e)
+        }
+        FieldElementExpression::Mult(box e, box FieldElementExpression::Number(n))
+        | FieldElementExpression::Mult(box FieldElementExpression::Number(n), box e)
+            if n == T::one() =>
+        {
+            remove_identity(e)
+        }
+        e => e,
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for ValueNumbering<'ast, T> {
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        let written = written_variables(&s);
+
+        let res = match s {
+            TypedStatement::Definition(TypedAssignee::Identifier(var), e) => {
+                let e = match self.fold_expression(e) {
+                    TypedExpression::FieldElement(e) => remove_identity(e).into(),
+                    e => e,
+                };
+
+                // removing an identity can leave a copy, which is eliminated the same way as by the copy eliminator
+                let representative = copy_source(&e).or_else(|| self.values.get(&e).cloned());
+
+                match representative {
+                    Some(representative) => {
+                        self.representatives.insert(var.id, representative);
+                        vec![]
+                    }
+                    None => {
+                        let var = self.fold_variable(var);
+
+                        if matches!(
+                            e,
+                            TypedExpression::FieldElement(_)
+                                | TypedExpression::Boolean(_)
+                                | TypedExpression::Uint(_)
+                        ) {
+                            self.values.insert(e.clone(), var.id.clone());
+                        }
+
+                        vec![TypedStatement::Definition(
+                            TypedAssignee::Identifier(var),
+                            e,
+                        )]
+                    }
+                }
+            }
+            s => fold_statement(self, s),
+        };
+
+        // the values read from the written variables change, so they are not reused by the following statements
+        if !written.is_empty() {
+            self.values.retain(|e, _| !reads(e.clone(), &written));
+        }
+
+        res
+    }
+
+    fn fold_block_expression<E: Fold<'ast, T>>(
+        &mut self,
+        block: BlockExpression<'ast, T, E>,
+    ) -> BlockExpression<'ast, T, E> {
+        // variables defined in a block are not in scope after it
+        let values = self.values.clone();
+        let block = fold_block_expression(self, block);
+        self.values = values;
+        block
+    }
+
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        // the map is kept flat: a representative is never eliminated itself
+        self.representatives.get(&n).cloned().unwrap_or(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    fn a(version: usize) -> FieldElementExpression<'static, Bn128Field> {
+        FieldElementExpression::Identifier(Identifier::from("a").version(version))
+    }
+
+    #[test]
+    fn identity() {
+        // a_2 = a_1 + 0
+        // b = a_2 * a_2
+        // return b
+
+        // expected:
+        // b = a_1 * a_1
+        // return b
+
+        let statements = vec![
+            TypedStatement::Definition(
+                Variable::field_element(Identifier::from("a").version(2)).into(),
+                FieldElementExpression::Add(
+                    box a(1),
+                    box FieldElementExpression::Number(Bn128Field::from(0)),
+                )
+                .into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("b").into(),
+                FieldElementExpression::Mult(box a(2), box a(2)).into(),
+            ),
+            TypedStatement::Return(vec![FieldElementExpression::Identifier("b".into()).into()]),
+        ];
+
+        let expected = vec![
+            TypedStatement::Definition(
+                Variable::field_element("b").into(),
+                FieldElementExpression::Mult(box a(1), box a(1)).into(),
+            ),
+            TypedStatement::Return(vec![FieldElementExpression::Identifier("b".into()).into()]),
+        ];
+
+        let mut value_numbering = ValueNumbering::default();
+
+        assert_eq!(
+            statements
+                .into_iter()
+                .flat_map(|s| value_numbering.fold_statement(s))
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn equal_expressions() {
+        // b = a_1 * a_1
+        // c = a_1 * a_1
+        // return b + c
+
+        // expected:
+        // b = a_1 * a_1
+        // return b + b
+
+        let square = FieldElementExpression::Mult(box a(1), box a(1));
+
+        let statements = vec![
+            TypedStatement::Definition(Variable::field_element("b").into(), square.clone().into()),
+            TypedStatement::Definition(Variable::field_element("c").into(), square.clone().into()),
+            TypedStatement::Return(vec![FieldElementExpression::Add(
+                box FieldElementExpression::Identifier("b".into()),
+                box FieldElementExpression::Identifier("c".into()),
+            )
+            .into()]),
+        ];
+
+        let expected = vec![
+            TypedStatement::Definition(Variable::field_element("b").into(), square.into()),
+            TypedStatement::Return(vec![FieldElementExpression::Add(
+                box FieldElementExpression::Identifier("b".into()),
+                box FieldElementExpression::Identifier("b".into()),
+            )
+            .into()]),
+        ];
+
+        let mut value_numbering = ValueNumbering::default();
+
+        assert_eq!(
+            statements
+                .into_iter()
+                .flat_map(|s| value_numbering.fold_statement(s))
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn element_write() {
+        // field[2] v = [a_1, a_1]
+        // b = v[0]
+        // v[0] = a_2
+        // c = v[0]
+        // return b + c
+
+        // `v[0]` reads a different value after the write, so `c` is kept
+
+        let v = || ArrayExpressionInner::Identifier("v".into()).annotate(Type::FieldElement, 2u32);
+
+        let statements = vec![
+            TypedStatement::Definition(
+                Variable::array("v", Type::FieldElement, 2u32).into(),
+                ArrayExpressionInner::Value(vec![a(1).into(), a(1).into()].into())
+                    .annotate(Type::FieldElement, 2u32)
+                    .into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("b").into(),
+                FieldElementExpression::select(v(), 0u32).into(),
+            ),
+            TypedStatement::Definition(
+                TypedAssignee::Select(
+                    box Variable::array("v", Type::FieldElement, 2u32).into(),
+                    box 0u32.into(),
+                ),
+                a(2).into(),
+            ),
+            TypedStatement::Definition(
+                Variable::field_element("c").into(),
+                FieldElementExpression::select(v(), 0u32).into(),
+            ),
+            TypedStatement::Return(vec![FieldElementExpression::Add(
+                box FieldElementExpression::Identifier("b".into()),
+                box FieldElementExpression::Identifier("c".into()),
+            )
+            .into()]),
+        ];
+
+        let mut value_numbering = ValueNumbering::default();
+
+        assert_eq!(
+            statements
+                .clone()
+                .into_iter()
+                .flat_map(|s| value_numbering.fold_statement(s))
+                .collect::<Vec<_>>(),
+            statements
+        );
+    }
+}
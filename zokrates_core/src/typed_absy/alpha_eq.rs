@@ -0,0 +1,160 @@
+//! Module containing the comparison of programs up to the numbering of SSA versions
+//!
+//! Two programs are alpha-equivalent if they only differ by a consistent renaming of the versions of
+//! their identifiers inside each function. This is useful to compare the output of the reducer without
+//! depending on the exact version counters it allocates.
+
+use crate::typed_absy::folder::Folder;
+use crate::typed_absy::{
+    CoreIdentifier, Identifier, TypedFunctionSymbol, TypedModule, TypedProgram,
+};
+use std::collections::HashMap;
+use zokrates_field::Field;
+
+impl<'ast, T: Field> TypedProgram<'ast, T> {
+    /// Returns whether `self` and `other` are equal up to a consistent renaming of SSA versions in each function
+    pub fn alpha_eq(&self, other: &Self) -> bool {
+        normalize(self.clone()) == normalize(other.clone())
+    }
+}
+
+// renumber the versions in each function of `p` in order of first occurrence
+fn normalize<T: Field>(p: TypedProgram<T>) -> TypedProgram<T> {
+    TypedProgram {
+        modules: p
+            .modules
+            .into_iter()
+            .map(|(id, m)| {
+                (
+                    id,
+                    TypedModule {
+                        functions: m
+                            .functions
+                            .into_iter()
+                            .map(|(key, symbol)| {
+                                (
+                                    key,
+                                    match symbol {
+                                        TypedFunctionSymbol::Here(f) => TypedFunctionSymbol::Here(
+                                            VersionNormalizer::default().fold_function(f),
+                                        ),
+                                        s => s,
+                                    },
+                                )
+                            })
+                            .collect(),
+                        ..m
+                    },
+                )
+            })
+            .collect(),
+        ..p
+    }
+}
+
+#[derive(Default)]
+struct VersionNormalizer<'ast> {
+    // the normalized version of each identifier found so far
+    versions: HashMap<Identifier<'ast>, usize>,
+    // the number of versions found so far for each core identifier
+    counts: HashMap<CoreIdentifier<'ast>, usize>,
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for VersionNormalizer<'ast> {
+    fn fold_name(&mut self, n: Identifier<'ast>) -> Identifier<'ast> {
+        let counts = &mut self.counts;
+
+        let version = *self.versions.entry(n.clone()).or_insert_with(|| {
+            let count = counts.entry(n.id.clone()).or_insert(0);
+            *count += 1;
+            *count - 1
+        });
+
+        Identifier { version, ..n }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typed_absy::{
+        DeclarationFunctionKey, DeclarationSignature, DeclarationType, DeclarationVariable,
+        FieldElementExpression, TypedFunction, TypedStatement, Variable,
+    };
+    use zokrates_field::Bn128Field;
+
+    type Operator = fn(
+        Box<FieldElementExpression<'static, Bn128Field>>,
+        Box<FieldElementExpression<'static, Bn128Field>>,
+    ) -> FieldElementExpression<'static, Bn128Field>;
+
+    // def main(field a) -> field:
+    //      a_DEFINED = a OP a
+    //      return a_RETURNED
+    fn program(defined: usize, returned: usize, op: Operator) -> TypedProgram<'static, Bn128Field> {
+        let signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .outputs(vec![DeclarationType::FieldElement]);
+
+        let main = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![
+                TypedStatement::Definition(
+                    Variable::field_element(Identifier::from("a").version(defined)).into(),
+                    op(
+                        box FieldElementExpression::Identifier("a".into()),
+                        box FieldElementExpression::Identifier("a".into()),
+                    )
+                    .into(),
+                ),
+                TypedStatement::Return(vec![FieldElementExpression::Identifier(
+                    Identifier::from("a").version(returned),
+                )
+                .into()]),
+            ],
+            signature: signature.clone(),
+        };
+
+        TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    functions: vec![(
+                        DeclarationFunctionKey::with_location("main", "main").signature(signature),
+                        TypedFunctionSymbol::Here(main),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    constants: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn renamed_versions() {
+        let p = program(1, 1, FieldElementExpression::Mult);
+
+        assert!(p.alpha_eq(&program(5, 5, FieldElementExpression::Mult)));
+    }
+
+    #[test]
+    fn inconsistent_versions() {
+        let p = program(1, 1, FieldElementExpression::Mult);
+
+        // the returned variable is not the defined one
+        assert!(!p.alpha_eq(&program(1, 2, FieldElementExpression::Mult)));
+        // the returned variable is the argument
+        assert!(!p.alpha_eq(&program(1, 0, FieldElementExpression::Mult)));
+    }
+
+    #[test]
+    fn different_structure() {
+        let p = program(1, 1, FieldElementExpression::Mult);
+
+        assert!(!p.alpha_eq(&program(5, 5, FieldElementExpression::Add)));
+    }
+}
@@ -557,6 +557,11 @@ pub fn fold_field_expression<'ast, T: Field, F: Folder<'ast, T>>(
 
             FieldElementExpression::Pos(box e)
         }
+        FieldElementExpression::BoolToField(box e) => {
+            let e = f.fold_boolean_expression(e);
+
+            FieldElementExpression::BoolToField(box e)
+        }
         FieldElementExpression::IfElse(c) => {
             match f.fold_if_else_expression(&Type::FieldElement, c) {
                 IfElseOrExpression::IfElse(s) => FieldElementExpression::IfElse(s),
@@ -837,6 +842,16 @@ pub fn fold_uint_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
 
             UExpressionInner::RightShift(box e, box by)
         }
+        UExpressionInner::RotateLeft(box e, by) => {
+            let e = f.fold_uint_expression(e);
+
+            UExpressionInner::RotateLeft(box e, by)
+        }
+        UExpressionInner::RotateRight(box e, by) => {
+            let e = f.fold_uint_expression(e);
+
+            UExpressionInner::RotateRight(box e, by)
+        }
         UExpressionInner::Not(box e) => {
             let e = f.fold_uint_expression(e);
 
@@ -5,6 +5,7 @@ use std::fmt;
 pub enum CoreIdentifier<'ast> {
     Source(&'ast str),
     Call(usize),
+    Subexpression(usize),
 }
 
 impl<'ast> fmt::Display for CoreIdentifier<'ast> {
@@ -12,6 +13,7 @@ impl<'ast> fmt::Display for CoreIdentifier<'ast> {
         match self {
             CoreIdentifier::Source(s) => write!(f, "{}", s),
             CoreIdentifier::Call(i) => write!(f, "#CALL_RETURN_AT_INDEX_{}", i),
+            CoreIdentifier::Subexpression(i) => write!(f, "#SUBEXPRESSION_{}", i),
         }
     }
 }
@@ -503,7 +503,12 @@ impl<'ast, T: Field> ArrayExpression<'ast, T> {
                     }
                 }?;
 
-                let inner_ty = res.0[0].get_type().0;
+                // an empty array keeps the target type
+                let inner_ty = res
+                    .0
+                    .first()
+                    .map(|e| e.get_type().0)
+                    .unwrap_or(target_inner_ty);
 
                 Ok(ArrayExpressionInner::Value(res).annotate(inner_ty, array_ty.size))
             }
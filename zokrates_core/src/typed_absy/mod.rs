@@ -6,6 +6,7 @@
 //! @date 2017
 
 pub mod abi;
+mod alpha_eq;
 pub mod folder;
 pub mod identifier;
 pub mod result_folder;
@@ -903,6 +904,7 @@ pub enum FieldElementExpression<'ast, T> {
     IfElse(IfElseExpression<'ast, T, Self>),
     Neg(Box<FieldElementExpression<'ast, T>>),
     Pos(Box<FieldElementExpression<'ast, T>>),
+    BoolToField(Box<BooleanExpression<'ast, T>>),
     FunctionCall(FunctionCallExpression<'ast, T, Self>),
     Member(MemberExpression<'ast, T, Self>),
     Select(SelectExpression<'ast, T, Self>),
@@ -1343,6 +1345,10 @@ impl<'ast, T: fmt::Display> fmt::Display for FieldElementExpression<'ast, T> {
             FieldElementExpression::Block(ref block) => write!(f, "{}", block),
             FieldElementExpression::Number(ref i) => write!(f, "{}f", i),
             FieldElementExpression::Identifier(ref var) => write!(f, "{}", var),
+            // subtractions are canonicalized to additions of negations during propagation, print them back as subtractions
+            FieldElementExpression::Add(ref lhs, box FieldElementExpression::Neg(ref rhs)) => {
+                write!(f, "({} - {})", lhs, rhs)
+            }
             FieldElementExpression::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
             FieldElementExpression::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
             FieldElementExpression::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
@@ -1350,6 +1356,7 @@ impl<'ast, T: fmt::Display> fmt::Display for FieldElementExpression<'ast, T> {
             FieldElementExpression::Pow(ref lhs, ref rhs) => write!(f, "{}**{}", lhs, rhs),
             FieldElementExpression::Neg(ref e) => write!(f, "(-{})", e),
             FieldElementExpression::Pos(ref e) => write!(f, "(+{})", e),
+            FieldElementExpression::BoolToField(ref e) => write!(f, "bool_to_field({})", e),
             FieldElementExpression::IfElse(ref c) => write!(f, "{}", c),
             FieldElementExpression::FunctionCall(ref function_call) => {
                 write!(f, "{}", function_call)
@@ -1379,6 +1386,8 @@ impl<'ast, T: fmt::Display> fmt::Display for UExpression<'ast, T> {
             UExpressionInner::Rem(ref lhs, ref rhs) => write!(f, "({} % {})", lhs, rhs),
             UExpressionInner::RightShift(ref e, ref by) => write!(f, "({} >> {})", e, by),
             UExpressionInner::LeftShift(ref e, ref by) => write!(f, "({} << {})", e, by),
+            UExpressionInner::RotateLeft(ref e, by) => write!(f, "rotl({}, {})", e, by),
+            UExpressionInner::RotateRight(ref e, by) => write!(f, "rotr({}, {})", e, by),
             UExpressionInner::Not(ref e) => write!(f, "!{}", e),
             UExpressionInner::Neg(ref e) => write!(f, "(-{})", e),
             UExpressionInner::Pos(ref e) => write!(f, "(+{})", e),
@@ -1693,6 +1702,36 @@ impl<'ast, T: Clone> IfElse<'ast, T> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Clone> IfElse<'ast, T> for TypedExpression<'ast, T> {
+    fn if_else(
+        condition: BooleanExpression<'ast, T>,
+        consequence: Self,
+        alternative: Self,
+    ) -> Self {
+        match (consequence, alternative) {
+            (TypedExpression::Boolean(c), TypedExpression::Boolean(a)) => {
+                BooleanExpression::if_else(condition, c, a).into()
+            }
+            (TypedExpression::FieldElement(c), TypedExpression::FieldElement(a)) => {
+                FieldElementExpression::if_else(condition, c, a).into()
+            }
+            (TypedExpression::Uint(c), TypedExpression::Uint(a)) => {
+                UExpression::if_else(condition, c, a).into()
+            }
+            (TypedExpression::Array(c), TypedExpression::Array(a)) => {
+                ArrayExpression::if_else(condition, c, a).into()
+            }
+            (TypedExpression::Struct(c), TypedExpression::Struct(a)) => {
+                StructExpression::if_else(condition, c, a).into()
+            }
+            (TypedExpression::Int(c), TypedExpression::Int(a)) => {
+                IntExpression::if_else(condition, c, a).into()
+            }
+            _ => unreachable!("branches of a conditional should have the same type"),
+        }
+    }
+}
+
 pub trait Select<'ast, T> {
     fn select<I: Into<UExpression<'ast, T>>>(array: ArrayExpression<'ast, T>, index: I) -> Self;
 }
@@ -588,6 +588,11 @@ pub fn fold_field_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
 
             FieldElementExpression::Pos(box e)
         }
+        FieldElementExpression::BoolToField(box e) => {
+            let e = f.fold_boolean_expression(e)?;
+
+            FieldElementExpression::BoolToField(box e)
+        }
         FieldElementExpression::IfElse(c) => {
             match f.fold_if_else_expression(&Type::FieldElement, c)? {
                 IfElseOrExpression::IfElse(c) => FieldElementExpression::IfElse(c),
@@ -916,6 +921,16 @@ pub fn fold_uint_expression_inner<'ast, T: Field, F: ResultFolder<'ast, T>>(
 
             UExpressionInner::RightShift(box e, box by)
         }
+        UExpressionInner::RotateLeft(box e, by) => {
+            let e = f.fold_uint_expression(e)?;
+
+            UExpressionInner::RotateLeft(box e, by)
+        }
+        UExpressionInner::RotateRight(box e, by) => {
+            let e = f.fold_uint_expression(e)?;
+
+            UExpressionInner::RotateRight(box e, by)
+        }
         UExpressionInner::Not(box e) => {
             let e = f.fold_uint_expression(e)?;
 
@@ -362,6 +362,7 @@ pub struct GStructType<S> {
     pub canonical_location: StructLocation,
     #[serde(skip)]
     pub location: Option<StructLocation>,
+    // members are kept in declaration order, which defines the layout of the struct once flattened and in the ABI
     pub members: Vec<GStructMember<S>>,
 }
 
@@ -114,6 +114,16 @@ impl<'ast, T: Field> UExpression<'ast, T> {
         UExpressionInner::RightShift(box self, box by).annotate(bitwidth)
     }
 
+    pub fn rotate_left(self, by: u32) -> UExpression<'ast, T> {
+        let bitwidth = self.bitwidth;
+        UExpressionInner::RotateLeft(box self, by).annotate(bitwidth)
+    }
+
+    pub fn rotate_right(self, by: u32) -> UExpression<'ast, T> {
+        let bitwidth = self.bitwidth;
+        UExpressionInner::RotateRight(box self, by).annotate(bitwidth)
+    }
+
     pub fn floor_sub(self, other: Self) -> UExpression<'ast, T> {
         let bitwidth = self.bitwidth;
         assert_eq!(bitwidth, other.bitwidth);
@@ -193,6 +203,8 @@ pub enum UExpressionInner<'ast, T> {
     FunctionCall(FunctionCallExpression<'ast, T, UExpression<'ast, T>>),
     LeftShift(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
     RightShift(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    RotateLeft(Box<UExpression<'ast, T>>, u32),
+    RotateRight(Box<UExpression<'ast, T>>, u32),
     IfElse(IfElseExpression<'ast, T, UExpression<'ast, T>>),
     Member(MemberExpression<'ast, T, UExpression<'ast, T>>),
     Select(SelectExpression<'ast, T, UExpression<'ast, T>>),
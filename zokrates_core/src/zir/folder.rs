@@ -164,6 +164,10 @@ pub fn fold_field_expression<'ast, T: Field, F: Folder<'ast, T>>(
             let alt = f.fold_field_expression(alt);
             FieldElementExpression::IfElse(box cond, box cons, box alt)
         }
+        FieldElementExpression::BoolToField(box e) => {
+            let e = f.fold_boolean_expression(e);
+            FieldElementExpression::BoolToField(box e)
+        }
     }
 }
 
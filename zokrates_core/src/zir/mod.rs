@@ -288,6 +288,7 @@ pub enum FieldElementExpression<'ast, T> {
         Box<FieldElementExpression<'ast, T>>,
         Box<FieldElementExpression<'ast, T>>,
     ),
+    BoolToField(Box<BooleanExpression<'ast, T>>),
 }
 
 /// An expression of type `bool`
@@ -416,6 +417,7 @@ impl<'ast, T: fmt::Display> fmt::Display for FieldElementExpression<'ast, T> {
             FieldElementExpression::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
             FieldElementExpression::Div(ref lhs, ref rhs) => write!(f, "({} / {})", lhs, rhs),
             FieldElementExpression::Pow(ref lhs, ref rhs) => write!(f, "{}**{}", lhs, rhs),
+            FieldElementExpression::BoolToField(ref e) => write!(f, "bool_to_field({})", e),
             FieldElementExpression::IfElse(ref condition, ref consequent, ref alternative) => {
                 write!(
                     f,
@@ -542,6 +544,7 @@ impl<'ast, T: fmt::Debug> fmt::Debug for FieldElementExpression<'ast, T> {
             }
             FieldElementExpression::Div(ref lhs, ref rhs) => write!(f, "Div({:?}, {:?})", lhs, rhs),
             FieldElementExpression::Pow(ref lhs, ref rhs) => write!(f, "Pow({:?}, {:?})", lhs, rhs),
+            FieldElementExpression::BoolToField(ref e) => write!(f, "BoolToField({:?})", e),
             FieldElementExpression::IfElse(ref condition, ref consequent, ref alternative) => {
                 write!(
                     f,
@@ -0,0 +1,44 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use std::io;
+use zokrates_common::Resolver;
+use zokrates_core::compile::{compile, CompilationArtifacts, CompileConfig, CompileErrorInner};
+use zokrates_field::Bn128Field;
+
+#[test]
+fn exceeds_backend_limit() {
+    let source = r#"
+		def main(private field a, private field b) -> field:
+			return a * a * b * b
+	"#
+    .to_string();
+
+    let config = CompileConfig::default().max_constraints(Some(2));
+
+    let res = compile::<Bn128Field, _>(
+        source.clone(),
+        "./path/to/file".into(),
+        None::<&dyn Resolver<io::Error>>,
+        &config,
+    );
+
+    let errors = res.unwrap_err().0;
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].value(),
+        CompileErrorInner::ExceedsBackendLimit(count, 2) if *count > 2
+    ));
+
+    // the same program compiles when there is no limit
+    let res: CompilationArtifacts<Bn128Field> = compile(
+        source,
+        "./path/to/file".into(),
+        None::<&dyn Resolver<io::Error>>,
+        &CompileConfig::default(),
+    )
+    .unwrap();
+
+    assert!(res.prog().constraint_count() > 2);
+}
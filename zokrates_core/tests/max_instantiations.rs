@@ -0,0 +1,81 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use std::io;
+use zokrates_common::Resolver;
+use zokrates_core::compile::{compile, CompilationArtifacts, CompileConfig, CompileErrorInner};
+use zokrates_field::Bn128Field;
+
+#[test]
+fn too_many_instantiations() {
+    let source = r#"
+		def foo<N>() -> u32:
+			return N
+
+		def main() -> u32:
+			u32 res = 0
+			for u32 i in 0..100 do
+				res = res + foo::<i>()
+			endfor
+			return res
+	"#
+    .to_string();
+
+    let config = CompileConfig::default().max_instantiations(Some(50));
+
+    let res = compile::<Bn128Field, _>(
+        source.clone(),
+        "./path/to/file".into(),
+        None::<&dyn Resolver<io::Error>>,
+        &config,
+    );
+
+    let errors = res.unwrap_err().0;
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].value(),
+        CompileErrorInner::AnalysisError(_)
+    ));
+    assert!(errors[0]
+        .value()
+        .to_string()
+        .contains("maximum allowed of 50 distinct instantiations"));
+
+    // the same program compiles when there is no limit
+    let _: CompilationArtifacts<Bn128Field> = compile(
+        source,
+        "./path/to/file".into(),
+        None::<&dyn Resolver<io::Error>>,
+        &CompileConfig::default(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn non_generic_functions_are_not_counted() {
+    let source = r#"
+		def foo() -> u32:
+			return 1
+
+		def bar() -> u32:
+			return 2
+
+		def baz() -> u32:
+			return 3
+
+		def main() -> u32:
+			return foo() + bar() + baz()
+	"#
+    .to_string();
+
+    let config = CompileConfig::default().max_instantiations(Some(2));
+
+    let _: CompilationArtifacts<Bn128Field> = compile(
+        source,
+        "./path/to/file".into(),
+        None::<&dyn Resolver<io::Error>>,
+        &config,
+    )
+    .unwrap();
+}
@@ -0,0 +1,85 @@
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use zokrates_core::typed_absy::{
+    DeclarationFunctionKey, DeclarationParameter, DeclarationSignature, DeclarationType,
+    DeclarationVariable, FieldElementExpression, GenericIdentifier, Identifier, TypedFunction,
+    TypedFunctionSymbol, TypedModule, TypedProgram, TypedStatement, UBitwidth, UExpressionInner,
+    Variable,
+};
+use zokrates_core::{reduce_function, ReducerConfig};
+use zokrates_field::Bn128Field;
+
+#[test]
+fn reduce_generic_function() {
+    // def foo<K>(field a) -> field:
+    //      for u32 i in 0..K do
+    //          a = a * a
+    //      endfor
+    //      return a
+
+    let signature = DeclarationSignature::new()
+        .generics(vec![Some(
+            GenericIdentifier::with_name("K").index(0).into(),
+        )])
+        .inputs(vec![DeclarationType::FieldElement])
+        .outputs(vec![DeclarationType::FieldElement]);
+
+    let a = || FieldElementExpression::Identifier("a".into());
+
+    let foo: TypedFunction<Bn128Field> = TypedFunction {
+        arguments: vec![DeclarationParameter {
+            id: DeclarationVariable::field_element("a"),
+            private: true,
+        }],
+        statements: vec![
+            TypedStatement::For(
+                Variable::uint("i", UBitwidth::B32),
+                0u32.into(),
+                UExpressionInner::Identifier("K".into()).annotate(UBitwidth::B32),
+                vec![TypedStatement::Definition(
+                    Variable::field_element("a").into(),
+                    FieldElementExpression::Mult(Box::new(a()), Box::new(a())).into(),
+                )],
+            ),
+            TypedStatement::Return(vec![a().into()]),
+        ],
+        signature: signature.clone(),
+    };
+
+    let p = TypedProgram {
+        main: "main".into(),
+        modules: vec![(
+            "main".into(),
+            TypedModule {
+                functions: vec![(
+                    DeclarationFunctionKey::with_location("main", "foo").signature(signature),
+                    TypedFunctionSymbol::Here(foo.clone()),
+                )]
+                .into_iter()
+                .collect(),
+                constants: Default::default(),
+            },
+        )]
+        .into_iter()
+        .collect(),
+    };
+
+    // with K == 2, the loop is unrolled into two squarings
+    let reduced = reduce_function(foo.clone(), vec![2], &p, &ReducerConfig::default()).unwrap();
+
+    assert_eq!(
+        reduced.statements.last(),
+        Some(&TypedStatement::Return(vec![
+            FieldElementExpression::Identifier(Identifier::from("a").version(5)).into()
+        ]))
+    );
+    assert_eq!(reduced.statements.len(), 3);
+
+    // the value of K must be provided
+    let error = reduce_function(foo, vec![], &p, &ReducerConfig::default()).unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("expects 1 generic parameters, found 0"));
+}
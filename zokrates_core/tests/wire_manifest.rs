@@ -0,0 +1,37 @@
+extern crate zokrates_common;
+extern crate zokrates_core;
+extern crate zokrates_field;
+
+use std::io;
+use zokrates_common::Resolver;
+use zokrates_core::compile::{compile, CompilationArtifacts, CompileConfig};
+use zokrates_field::Bn128Field;
+
+#[test]
+fn deterministic_manifest() {
+    let source = r#"
+		def main(private field a, field[2] b) -> field:
+			field c = a * b[0]
+			assert(c != b[1])
+			return c * c
+	"#
+    .to_string();
+
+    let compile_manifest = || {
+        let artifacts: CompilationArtifacts<Bn128Field> = compile(
+            source.clone(),
+            "./path/to/file".into(),
+            None::<&dyn Resolver<io::Error>>,
+            &CompileConfig::default(),
+        )
+        .unwrap();
+        artifacts.wire_manifest().to_string()
+    };
+
+    let manifest = compile_manifest();
+
+    assert!(manifest.starts_with(
+        "~one one\n_0 private_input a\n_1 public_input b[0]\n_2 public_input b[1]\n~out_0 output\n"
+    ));
+    assert_eq!(manifest, compile_manifest());
+}
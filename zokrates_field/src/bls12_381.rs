@@ -7,3 +7,8 @@ prime_field!(
 use bellman_ce::pairing::bls12_381::{Bls12, Fq2};
 #[cfg(feature = "bellman")]
 bellman_extensions!(Bls12, Fq2);
+
+#[cfg(feature = "ark")]
+use ark_bls12_381::Bls12_381;
+#[cfg(feature = "ark")]
+ark_extensions!(Bls12_381);
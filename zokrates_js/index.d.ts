@@ -12,7 +12,11 @@ declare module 'zokrates-js' {
 
    export interface CompileConfig {
       allow_unconstrained_variables?: boolean,
-      isolate_branches?: boolean
+      isolate_branches?: boolean,
+      reorder_definitions?: boolean,
+      max_constraints?: number,
+      max_instantiations?: number,
+      merge_public_inputs?: boolean
    }
 
   export interface CompileOptions {
@@ -12,7 +12,9 @@ use zokrates_core::imports::Error;
 use zokrates_core::ir;
 use zokrates_core::proof_system::bellman::Bellman;
 use zokrates_core::proof_system::groth16::G16;
-use zokrates_core::proof_system::{Backend, Proof, Scheme, SolidityAbi, SolidityCompatibleScheme};
+use zokrates_core::proof_system::{
+    Backend, Proof, Scheme, SolidityAbi, SolidityCompatibleScheme, SolidityInputs,
+};
 use zokrates_core::typed_absy::abi::Abi;
 use zokrates_core::typed_absy::types::ConcreteSignature as Signature;
 use zokrates_field::Bn128Field;
@@ -174,6 +176,7 @@ pub fn export_solidity_verifier(vk: JsValue, abi_version: JsValue) -> Result<JsV
     let verifier = <G16 as SolidityCompatibleScheme<Bn128Field>>::export_solidity_verifier(
         vk.into_serde().unwrap(),
         abi_version,
+        SolidityInputs::Fixed,
     );
 
     Ok(JsValue::from_str(verifier.as_str()))